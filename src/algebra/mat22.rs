@@ -1,6 +1,6 @@
-use std::ops::Mul;
+use std::ops::{Add, Mul, Sub};
 
-use super::{equals_float, Float, Vec2};
+use super::{Float, Vec2, FLOADT_TOLERANCE};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Mat22 {
@@ -15,15 +15,32 @@ impl Mat22 {
         Self { m00, m01, m10, m11 }
     }
 
+    pub fn identity() -> Self {
+        Mat22::new(1.0, 0.0, 0.0, 1.0)
+    }
+
     pub fn rotation(radian: Float) -> Self {
         let cos = radian.cos();
         let sin = radian.sin();
         Mat22::new(cos, -sin, sin, cos)
     }
 
+    pub fn transpose(&self) -> Self {
+        Mat22::new(self.m00, self.m10, self.m01, self.m11)
+    }
+
+    pub fn determinant(&self) -> Float {
+        self.m00 * self.m11 - self.m01 * self.m10
+    }
+
     pub fn inverse(&self) -> Option<Self> {
-        let det = self.m00 * self.m11 - self.m01 * self.m10;
-        if equals_float(det, 0.0) {
+        self.inverse_with_tolerance(FLOADT_TOLERANCE)
+    }
+
+    // 行列式相对于矩阵元素的量级不超过 tolerance 时视为奇异，与长度单位无关。
+    pub fn inverse_with_tolerance(&self, tolerance: Float) -> Option<Self> {
+        let det = self.determinant();
+        if self.is_singular(det, tolerance) {
             None
         } else {
             Some(Mat22::new(
@@ -34,6 +51,27 @@ impl Mat22 {
             ))
         }
     }
+
+    // 求解 Ax = b，矩阵奇异时返回 None。
+    pub fn solve(&self, b: Vec2) -> Option<Vec2> {
+        self.solve_with_tolerance(b, FLOADT_TOLERANCE)
+    }
+
+    pub fn solve_with_tolerance(&self, b: Vec2, tolerance: Float) -> Option<Vec2> {
+        let det = self.determinant();
+        if self.is_singular(det, tolerance) {
+            None
+        } else {
+            Some(Vec2::new(
+                (self.m11 * b.x - self.m01 * b.y) / det,
+                (self.m00 * b.y - self.m10 * b.x) / det,
+            ))
+        }
+    }
+
+    fn is_singular(&self, det: Float, tolerance: Float) -> bool {
+        det.abs() <= tolerance * ((self.m00 * self.m11).abs() + (self.m01 * self.m10).abs())
+    }
 }
 
 impl PartialEq for Mat22 {
@@ -47,6 +85,32 @@ impl PartialEq for Mat22 {
 
 impl Eq for Mat22 {}
 
+impl Add for Mat22 {
+    type Output = Mat22;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Mat22::new(
+            self.m00 + rhs.m00,
+            self.m01 + rhs.m01,
+            self.m10 + rhs.m10,
+            self.m11 + rhs.m11,
+        )
+    }
+}
+
+impl Sub for Mat22 {
+    type Output = Mat22;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Mat22::new(
+            self.m00 - rhs.m00,
+            self.m01 - rhs.m01,
+            self.m10 - rhs.m10,
+            self.m11 - rhs.m11,
+        )
+    }
+}
+
 impl Mul for Mat22 {
     type Output = Mat22;
 
@@ -1,8 +1,8 @@
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use super::{equals_float, Float};
+use super::{equals_float, Float, FLOADT_TOLERANCE};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Vec2 {
     pub x: Float,
     pub y: Float,
@@ -16,6 +16,54 @@ impl Vec2 {
     pub fn length_squared(self) -> Float {
         self.x * self.x + self.y * self.y
     }
+
+    pub fn length(self) -> Float {
+        self.length_squared().sqrt()
+    }
+
+    pub fn distance(self, other: Self) -> Float {
+        (other - self).length()
+    }
+
+    pub fn normalize(self) -> Self {
+        self / self.length()
+    }
+
+    pub fn try_normalize(self) -> Option<Self> {
+        self.try_normalize_with_tolerance(FLOADT_TOLERANCE)
+    }
+
+    // 长度小于 tolerance 时无法确定方向，返回 None。
+    pub fn try_normalize_with_tolerance(self, tolerance: Float) -> Option<Self> {
+        let length = self.length();
+        if length < tolerance {
+            None
+        } else {
+            Some(self / length)
+        }
+    }
+
+    pub fn dot(self, rhs: Self) -> Float {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    pub fn cross(self, rhs: Self) -> Float {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    // 标量与向量的叉积 s × v，例如角速度 ω 与力臂 r 的叉积即为线速度。
+    pub fn scalar_cross(s: Float, v: Self) -> Self {
+        Self::new(-s * v.y, s * v.x)
+    }
+
+    // 逆时针旋转 90°。
+    pub fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    pub fn lerp(self, other: Self, t: Float) -> Self {
+        self + (other - self) * t
+    }
 }
 
 impl PartialEq for Vec2 {
@@ -48,11 +96,22 @@ impl Sub for Vec2 {
     }
 }
 
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
 impl Mul for Vec2 {
     type Output = Float;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        self.x * rhs.x + self.y * rhs.y
+        self.dot(rhs)
     }
 }
 
@@ -77,3 +136,35 @@ impl Div<Float> for Vec2 {
         }
     }
 }
+
+impl Mul<Vec2> for Float {
+    type Output = Vec2;
+
+    fn mul(self, rhs: Vec2) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<Float> for Vec2 {
+    fn mul_assign(&mut self, rhs: Float) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<Float> for Vec2 {
+    fn div_assign(&mut self, rhs: Float) {
+        *self = *self / rhs;
+    }
+}
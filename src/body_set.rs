@@ -0,0 +1,119 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BodyHandle {
+    index: u32,
+    generation: u32,
+}
+
+impl BodyHandle {
+    pub(crate) fn slot(&self) -> usize {
+        self.index as usize
+    }
+}
+
+struct Slot {
+    generation: u32,
+    dense_index: Option<usize>,
+}
+
+// 物体紧凑地存放在 bodies 中，便于宽检测按下标遍历；句柄经由 slots 间接定位，
+// 删除物体时与末尾元素交换，其他物体的句柄保持有效。
+pub(crate) struct BodySet<T> {
+    bodies: Vec<T>,
+    handles: Vec<BodyHandle>,
+    slots: Vec<Slot>,
+    free_slots: Vec<u32>,
+}
+
+impl<T> BodySet<T> {
+    pub fn new() -> Self {
+        Self {
+            bodies: Vec::new(),
+            handles: Vec::new(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+        }
+    }
+
+    pub fn insert(&mut self, body: T) -> BodyHandle {
+        let dense_index = self.bodies.len();
+        let handle = match self.free_slots.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.dense_index = Some(dense_index);
+                BodyHandle {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    dense_index: Some(dense_index),
+                });
+                BodyHandle {
+                    index: (self.slots.len() - 1) as u32,
+                    generation: 0,
+                }
+            }
+        };
+
+        self.bodies.push(body);
+        self.handles.push(handle);
+        handle
+    }
+
+    pub fn remove(&mut self, handle: BodyHandle) -> Option<T> {
+        let dense_index = self.index_of(handle)?;
+        let slot = &mut self.slots[handle.index as usize];
+        slot.generation = slot.generation.wrapping_add(1);
+        slot.dense_index = None;
+        self.free_slots.push(handle.index);
+
+        self.handles.swap_remove(dense_index);
+        if let Some(moved) = self.handles.get(dense_index) {
+            self.slots[moved.index as usize].dense_index = Some(dense_index);
+        }
+
+        Some(self.bodies.swap_remove(dense_index))
+    }
+
+    pub fn get(&self, handle: BodyHandle) -> Option<&T> {
+        self.index_of(handle).map(|index| &self.bodies[index])
+    }
+
+    pub fn get_mut(&mut self, handle: BodyHandle) -> Option<&mut T> {
+        self.index_of(handle)
+            .map(move |index| &mut self.bodies[index])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (BodyHandle, &T)> {
+        self.handles.iter().copied().zip(self.bodies.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (BodyHandle, &mut T)> {
+        self.handles.iter().copied().zip(self.bodies.iter_mut())
+    }
+
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    pub fn handles(&self) -> &[BodyHandle] {
+        &self.handles
+    }
+
+    pub fn bodies(&self) -> &[T] {
+        &self.bodies
+    }
+
+    pub fn bodies_mut(&mut self) -> &mut [T] {
+        &mut self.bodies
+    }
+
+    pub fn index_of(&self, handle: BodyHandle) -> Option<usize> {
+        self.slots
+            .get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.dense_index)
+    }
+}
@@ -1,53 +1,628 @@
+use std::{collections::HashMap, marker::PhantomData};
+
 use crate::{
-    algebra::Float,
-    detection_broad_phase::{detect_by_broad_phase, ShapeIndexPair},
-    shapes::{Bounded, Collider, RigidBody, RigidBodyLike, Wall},
+    algebra::{Float, Vec2},
+    body_set::BodySet,
+    contact_solver::ContactSolver,
+    detection_broad_phase::{Aabb, BroadPhase, DynamicTree, SweepAndPrune},
+    detection_continuous::solve_time_of_impact,
+    integrator::integrate,
+    shapes::{
+        Body, BodyType, Bounded, Chain, Collider, CollisionFilter, Fixture, MassLike, Material,
+        MaterialLike, Orientation, RigidBody, RigidBodyLike, Segment, Shape, Transform, Wall,
+    },
+};
+pub use crate::{
+    body_set::BodyHandle,
+    contact_solver::SolverConfig,
+    integrator::{ForceField, Integrator},
 };
 
+pub type World = Box<Shape>;
+
+// 宽检测在 build 时才创建，以便使用最终的 SolverConfig。
+type BroadPhaseFactory = dyn FnOnce(&SolverConfig) -> std::boxed::Box<dyn BroadPhase>;
+
+// step 以固定的 time_step 推进模拟，每步再均分为 sub_steps 个子步；
+// 一次调用最多运行 max_steps 步，超出的时间被舍弃，以免模拟跟不上时越积越多。
+// time_step 不为正数时 step 不运行任何一步，也不累计时间；sub_steps 为 0 时按 1 处理。
+#[derive(Debug, Clone, Copy)]
+pub struct StepConfig {
+    pub time_step: Float,
+    pub max_steps: usize,
+    pub sub_steps: usize,
+}
+
+impl StepConfig {
+    fn has_valid_time_step(&self) -> bool {
+        self.time_step > 0.0 && self.time_step.is_finite()
+    }
+}
+
+impl Default for StepConfig {
+    fn default() -> Self {
+        Self {
+            time_step: 1.0 / 60.0,
+            max_steps: 8,
+            sub_steps: 1,
+        }
+    }
+}
+
+// 宽检测以夹具为单位，每个夹具在 proxies 中占有一个代理句柄。
+struct FixtureProxy {
+    body: BodyHandle,
+    fixture: usize,
+}
+
 pub struct Box<T>
 where
-    T: Collider + Bounded + RigidBodyLike,
+    T: Collider + Bounded + MassLike,
 {
-    pub wall_left: Wall,
-    pub wall_top: Wall,
-    pub wall_right: Wall,
-    pub wall_bottom: Wall,
-    shapes: Vec<T>,
+    pub walls: Vec<Wall>,
+    gravity: Vec2,
+    integrator: Integrator,
+    force_field: Option<std::boxed::Box<ForceField>>,
+    bodies: BodySet<Body<T>>,
+    proxies: BodySet<FixtureProxy>,
+    broad_phase: std::boxed::Box<dyn BroadPhase>,
+    // 相交的夹具对所属的物体对，代理移除后仍能据此得出结束相交的物体对。
+    fixture_overlaps: HashMap<(BodyHandle, BodyHandle), (BodyHandle, BodyHandle)>,
+    // 各物体对之间相交的夹具对数目。
+    overlaps: HashMap<(BodyHandle, BodyHandle), usize>,
+    began_overlaps: Vec<(BodyHandle, BodyHandle)>,
+    ended_overlaps: Vec<(BodyHandle, BodyHandle)>,
+    solver: ContactSolver,
+    step_config: StepConfig,
+    accumulator: Float,
+    previous_transforms: HashMap<BodyHandle, Transform>,
 }
 
 impl<T> Box<T>
 where
-    T: Collider + Bounded + RigidBodyLike,
+    T: Collider + Bounded + MassLike,
 {
+    pub fn builder() -> BoxBuilder<T> {
+        BoxBuilder::new()
+    }
+
+    // 插入单个形状组成的刚体。
+    pub fn insert(&mut self, shape: impl Into<T>) -> BodyHandle {
+        self.insert_body(Body::from_shape(shape.into()))
+    }
+
+    // 夹具的代理在下一帧宽检测时分配，此后经 get_mut 加入的夹具同样如此。
+    pub fn insert_body(&mut self, mut body: Body<T>) -> BodyHandle {
+        for fixture in body.fixtures_mut() {
+            fixture.proxy = None;
+        }
+        body.synchronize_fixtures();
+        self.bodies.insert(body)
+    }
+
+    // 链条的各条线段作为同一个静态刚体的夹具插入，夹具的顺序与线段的顺序一致。
+    pub fn insert_chain(&mut self, chain: &Chain) -> BodyHandle
+    where
+        Segment: Into<T>,
+    {
+        let mut body = Body::new(RigidBody {
+            body_type: BodyType::Static,
+            ..*chain.rigid_body()
+        });
+        // 线段的刚体即链条的刚体，夹具位于原点。
+        for segment in chain.segments() {
+            let mut fixture = Fixture::new(segment.into());
+            fixture.offset = Vec2::default();
+            fixture.angle = 0.0;
+            body.add_fixture(fixture);
+        }
+        self.insert_body(body)
+    }
+
+    pub fn remove(&mut self, handle: BodyHandle) -> Option<Body<T>> {
+        let body = self.bodies.remove(handle)?;
+        for proxy in body.fixtures().iter().filter_map(|fixture| fixture.proxy) {
+            self.broad_phase.remove(proxy);
+            self.proxies.remove(proxy);
+        }
+        self.previous_transforms.remove(&handle);
+        Some(body)
+    }
+
+    pub fn get(&self, handle: BodyHandle) -> Option<&Body<T>> {
+        self.bodies.get(handle)
+    }
+
+    pub fn get_mut(&mut self, handle: BodyHandle) -> Option<&mut Body<T>> {
+        self.bodies.get_mut(handle)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (BodyHandle, &Body<T>)> {
+        self.bodies.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (BodyHandle, &mut Body<T>)> {
+        self.bodies.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bodies.len() == 0
+    }
+
+    pub fn gravity(&self) -> Vec2 {
+        self.gravity
+    }
+
+    pub fn set_gravity(&mut self, gravity: Vec2) {
+        self.gravity = gravity;
+    }
+
+    pub fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        self.integrator = integrator;
+    }
+
+    pub fn set_force_field(&mut self, force_field: impl Fn(&RigidBody) -> Vec2 + 'static) {
+        self.force_field = Some(std::boxed::Box::new(force_field));
+    }
+
+    pub fn clear_force_field(&mut self) {
+        self.force_field = None;
+    }
+
+    pub fn solver_config(&self) -> &SolverConfig {
+        &self.solver.config
+    }
+
+    pub fn solver_config_mut(&mut self) -> &mut SolverConfig {
+        &mut self.solver.config
+    }
+
+    pub fn step_config(&self) -> &StepConfig {
+        &self.step_config
+    }
+
+    pub fn step_config_mut(&mut self) -> &mut StepConfig {
+        &mut self.step_config
+    }
+
+    // 尚未模拟的时间占一步的比例，渲染时据此在前后两步的位姿间插值。
+    pub fn interpolation_alpha(&self) -> Float {
+        if !self.step_config.has_valid_time_step() {
+            return 0.0;
+        }
+        self.accumulator / self.step_config.time_step
+    }
+
+    // 最近一步开始前质心的位姿；此后插入的物体取其当前位姿。
+    pub fn previous_transform(&self, handle: BodyHandle) -> Option<Transform> {
+        let current = self.bodies.get(handle)?.rigid_body().transform();
+        Some(
+            self.previous_transforms
+                .get(&handle)
+                .copied()
+                .unwrap_or(current),
+        )
+    }
+
+    pub fn interpolated_transform(&self, handle: BodyHandle) -> Option<Transform> {
+        let current = self.bodies.get(handle)?.rigid_body().transform();
+        let previous = self.previous_transform(handle)?;
+        Some(previous.lerp(current, self.interpolation_alpha()))
+    }
+
+    // 按经过的真实时间推进模拟，返回运行的步数。调用前施加的外力作用于本次运行的每个子步，
+    // 调用结束后清零；本次未运行任何一步时保留，与下次调用前施加的外力累加。
+    pub fn step(&mut self, elapsed: Float) -> usize {
+        let StepConfig {
+            time_step,
+            max_steps,
+            sub_steps,
+        } = self.step_config;
+        if !self.step_config.has_valid_time_step() {
+            return 0;
+        }
+        let sub_steps = sub_steps.max(1);
+        let forces: Vec<(Vec2, Float)> = self
+            .bodies
+            .bodies()
+            .iter()
+            .map(|body| (body.rigid_body().force, body.rigid_body().torque))
+            .collect();
+
+        self.accumulator += elapsed;
+        let mut steps = 0;
+        while self.accumulator >= time_step && steps < max_steps {
+            self.previous_transforms = self
+                .bodies
+                .iter()
+                .map(|(handle, body)| (handle, body.rigid_body().transform()))
+                .collect();
+            for _ in 0..sub_steps {
+                for (body, &(force, torque)) in self.bodies.bodies_mut().iter_mut().zip(&forces) {
+                    let body = body.rigid_body_mut();
+                    body.force = force;
+                    body.torque = torque;
+                }
+                self.next_frame(time_step / sub_steps as Float);
+            }
+            self.accumulator -= time_step;
+            steps += 1;
+        }
+        if self.accumulator >= time_step {
+            self.accumulator %= time_step;
+        }
+        if steps == 0 {
+            return steps;
+        }
+
+        for body in self.bodies.bodies_mut() {
+            let body = body.rigid_body_mut();
+            body.force = Vec2::default();
+            body.torque = 0.0;
+        }
+        steps
+    }
+
+    // 包围盒与给定区域相交的物体，包围盒在每帧宽检测时更新。
+    pub fn query_region(&self, region: &Aabb) -> Vec<BodyHandle> {
+        let mut handles: Vec<BodyHandle> = self
+            .broad_phase
+            .query(region)
+            .into_iter()
+            .filter_map(|proxy| Some(self.proxies.get(proxy)?.body))
+            .collect();
+        handles.sort_unstable();
+        handles.dedup();
+        handles
+    }
+
+    // 上一帧中包围盒开始相交与不再相交的物体对，句柄较小者在前；
+    // 两个物体之间任意一对夹具的包围盒相交即视为相交。
+    pub fn began_overlaps(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.began_overlaps
+    }
+
+    pub fn ended_overlaps(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.ended_overlaps
+    }
+
     pub fn next_frame(&mut self, duration: Float) {
-        for shape in self.shapes.iter_mut() {
-            drive_particle(shape.rigid_body_mut(), duration);
+        let has_bullet = self.bodies.iter().any(|(_, body)| body.rigid_body().bullet);
+        let starts: Vec<Transform> = if has_bullet {
+            self.bodies
+                .iter()
+                .map(|(_, body)| body.rigid_body().transform())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        for body in self.bodies.bodies_mut() {
+            integrate(
+                body.rigid_body_mut(),
+                self.integrator,
+                self.gravity,
+                self.force_field.as_deref(),
+                duration,
+            );
+            body.synchronize_fixtures();
         }
 
-        for index_pair in detect_by_broad_phase(&self.shapes.iter().collect()) {
-            let (shape1, shape2) = self.get_shape_pair_mut(index_pair);
-            shape1.collide_with(shape2);
+        if has_bullet {
+            solve_time_of_impact(
+                self.bodies.bodies_mut(),
+                &self.walls,
+                &starts,
+                &self.solver.config,
+            );
         }
 
-        for shape in self.shapes.iter_mut() {
-            shape.collide_with(&mut self.wall_left);
-            shape.collide_with(&mut self.wall_top);
-            shape.collide_with(&mut self.wall_right);
-            shape.collide_with(&mut self.wall_bottom);
+        self.update_broad_phase();
+
+        let handles = self.bodies.handles();
+        let bodies = self.bodies.bodies();
+        // 夹具对按代理句柄排序，使同一对夹具每帧的检测顺序一致，接触点的特征编号才能前后对应；
+        // 各夹具对的求解顺序也随之固定，求解结果不受宽检测内部顺序的影响。
+        let pairs: Vec<((usize, usize), (usize, usize))> = self
+            .broad_phase
+            .pairs()
+            .into_iter()
+            .filter_map(|(proxy1, proxy2)| {
+                let (proxy1, proxy2) = (self.proxies.get(proxy1)?, self.proxies.get(proxy2)?);
+                Some((
+                    (self.bodies.index_of(proxy1.body)?, proxy1.fixture),
+                    (self.bodies.index_of(proxy2.body)?, proxy2.fixture),
+                ))
+            })
+            .collect();
+
+        // 同一物体的夹具之间、静态与运动学物体之间、以及它们与墙体之间不产生接触；
+        // 被碰撞过滤器排除的夹具对在窄检测之前丢弃。
+        for ((index1, fixture1), (index2, fixture2)) in pairs {
+            let (body1, body2) = (&bodies[index1], &bodies[index2]);
+            if index1 == index2
+                || !body1.rigid_body().is_dynamic() && !body2.rigid_body().is_dynamic()
+            {
+                continue;
+            }
+            let (fixture1, fixture2) = match (
+                body1.fixtures().get(fixture1),
+                body2.fixtures().get(fixture2),
+            ) {
+                (Some(shape1), Some(shape2)) => ((fixture1, shape1), (fixture2, shape2)),
+                _ => continue,
+            };
+            if !fixture1.1.filter.should_collide(&fixture2.1.filter) {
+                continue;
+            }
+            if let Some(info) = fixture1
+                .1
+                .shape
+                .detect_with(&fixture2.1.shape, &self.solver.config)
+            {
+                self.solver.add_body_contact(
+                    (index1, (handles[index1], fixture1.0), fixture1.1.material()),
+                    (index2, (handles[index2], fixture2.0), fixture2.1.material()),
+                    info,
+                );
+            }
+        }
+
+        for (index, body) in bodies.iter().enumerate() {
+            if !body.rigid_body().is_dynamic() {
+                continue;
+            }
+            for (fixture_index, fixture) in body.fixtures().iter().enumerate() {
+                for (wall_index, wall) in self.walls.iter().enumerate() {
+                    if !fixture.filter.should_collide(&wall.filter) {
+                        continue;
+                    }
+                    if let Some(info) = fixture.shape.detect_with_wall(wall, &self.solver.config) {
+                        self.solver.add_wall_contact(
+                            (wall_index, wall.material()),
+                            (index, (handles[index], fixture_index), fixture.material()),
+                            info,
+                        );
+                    }
+                }
+            }
+        }
+
+        self.solver.solve(self.bodies.bodies_mut());
+        for body in self.bodies.bodies_mut() {
+            body.synchronize_fixtures();
+        }
+    }
+
+    // 更新各夹具的包围盒，尚未分配代理的夹具在此插入宽检测；
+    // 再由夹具对得出物体对相交状态的变化。
+    // 夹具经 get_mut 被替换、删除或重排后，指向的夹具已不存在或不再持有该代理的代理先被移除。
+    fn update_broad_phase(&mut self) {
+        let bodies = &self.bodies;
+        let stale: Vec<BodyHandle> = self
+            .proxies
+            .iter()
+            .filter(|(proxy, fixture_proxy)| {
+                bodies
+                    .get(fixture_proxy.body)
+                    .and_then(|body| body.fixtures().get(fixture_proxy.fixture))
+                    .is_none_or(|fixture| fixture.proxy != Some(*proxy))
+            })
+            .map(|(proxy, _)| proxy)
+            .collect();
+        for proxy in stale {
+            self.broad_phase.remove(proxy);
+            self.proxies.remove(proxy);
+        }
+
+        for (handle, body) in self.bodies.iter_mut() {
+            for (index, fixture) in body.fixtures_mut().iter_mut().enumerate() {
+                let aabb = Aabb::of(&fixture.shape);
+                // 从其他物体或其他位置移来的夹具所持有的代理不属于它，需重新分配。
+                match fixture.proxy {
+                    Some(proxy)
+                        if self.proxies.get(proxy).is_some_and(|fixture_proxy| {
+                            fixture_proxy.body == handle && fixture_proxy.fixture == index
+                        }) =>
+                    {
+                        self.broad_phase.set_aabb(proxy, aabb)
+                    }
+                    _ => {
+                        let proxy = self.proxies.insert(FixtureProxy {
+                            body: handle,
+                            fixture: index,
+                        });
+                        self.broad_phase.insert(proxy, aabb);
+                        fixture.proxy = Some(proxy);
+                    }
+                }
+            }
+        }
+        self.broad_phase.update_pairs();
+
+        // 先计入开始相交的夹具对，同一物体对的夹具对一个开始、一个结束时物体对保持相交。
+        self.began_overlaps.clear();
+        for &(proxy1, proxy2) in self.broad_phase.began() {
+            let (body1, body2) = match (self.proxies.get(proxy1), self.proxies.get(proxy2)) {
+                (Some(proxy1), Some(proxy2)) => (proxy1.body, proxy2.body),
+                _ => continue,
+            };
+            if body1 == body2 {
+                continue;
+            }
+            let bodies = (body1.min(body2), body1.max(body2));
+            self.fixture_overlaps.insert((proxy1, proxy2), bodies);
+            let count = self.overlaps.entry(bodies).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                self.began_overlaps.push(bodies);
+            }
+        }
+        self.ended_overlaps.clear();
+        for pair in self.broad_phase.ended() {
+            let bodies = match self.fixture_overlaps.remove(pair) {
+                Some(bodies) => bodies,
+                None => continue,
+            };
+            let count = self.overlaps.get_mut(&bodies).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.overlaps.remove(&bodies);
+                self.ended_overlaps.push(bodies);
+            }
+        }
+        self.began_overlaps.sort_unstable();
+        self.ended_overlaps.sort_unstable();
+    }
+}
+
+// 边界由若干半平面组成；bounds 设置的四面轴对齐墙体使用 wall_material 与 wall_filter。
+pub struct BoxBuilder<T> {
+    wall_material: Material,
+    wall_filter: CollisionFilter,
+    bounds: Option<(Float, Float, Float, Float)>,
+    walls: Vec<Wall>,
+    gravity: Vec2,
+    integrator: Integrator,
+    force_field: Option<std::boxed::Box<ForceField>>,
+    solver_config: SolverConfig,
+    step_config: StepConfig,
+    broad_phase: std::boxed::Box<BroadPhaseFactory>,
+    shapes: PhantomData<T>,
+}
+
+impl<T> BoxBuilder<T>
+where
+    T: Collider + Bounded + MassLike,
+{
+    pub fn new() -> Self {
+        Self {
+            wall_material: Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
+            wall_filter: CollisionFilter::default(),
+            bounds: None,
+            walls: Vec::new(),
+            gravity: Vec2::default(),
+            integrator: Integrator::default(),
+            force_field: None,
+            solver_config: SolverConfig::default(),
+            step_config: StepConfig::default(),
+            broad_phase: std::boxed::Box::new(|_: &SolverConfig| {
+                std::boxed::Box::new(SweepAndPrune::new()) as std::boxed::Box<dyn BroadPhase>
+            }),
+            shapes: PhantomData,
         }
     }
 
-    fn get_shape_pair_mut(&mut self, index_pair: ShapeIndexPair) -> (&mut T, &mut T) {
-        let mut shapes = self.shapes.iter_mut();
-        let shape1 = shapes.nth(index_pair.0).unwrap();
-        let shape2 = shapes.nth(index_pair.1).unwrap();
-        return (shape1, shape2);
+    pub fn bounds(mut self, left: Float, bottom: Float, right: Float, top: Float) -> Self {
+        self.bounds = Some((left, bottom, right, top));
+        self
+    }
+
+    pub fn wall(mut self, wall: Wall) -> Self {
+        self.walls.push(wall);
+        self
+    }
+
+    pub fn gravity(mut self, gravity: Vec2) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn integrator(mut self, integrator: Integrator) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    pub fn force_field(mut self, force_field: impl Fn(&RigidBody) -> Vec2 + 'static) -> Self {
+        self.force_field = Some(std::boxed::Box::new(force_field));
+        self
+    }
+
+    pub fn solver_config(mut self, solver_config: SolverConfig) -> Self {
+        self.solver_config = solver_config;
+        self
+    }
+
+    pub fn step_config(mut self, step_config: StepConfig) -> Self {
+        self.step_config = step_config;
+        self
+    }
+
+    pub fn broad_phase(mut self, broad_phase: impl BroadPhase + 'static) -> Self {
+        self.broad_phase = std::boxed::Box::new(move |_: &SolverConfig| {
+            std::boxed::Box::new(broad_phase) as std::boxed::Box<dyn BroadPhase>
+        });
+        self
+    }
+
+    // 使用动态树作为宽检测，包围盒放大的距离取自 SolverConfig::aabb_margin。
+    pub fn dynamic_tree(mut self) -> Self {
+        self.broad_phase = std::boxed::Box::new(|config: &SolverConfig| {
+            std::boxed::Box::new(DynamicTree::with_margin(config.aabb_margin))
+                as std::boxed::Box<dyn BroadPhase>
+        });
+        self
+    }
+
+    pub fn wall_material(mut self, material: Material) -> Self {
+        self.wall_material = material;
+        self
+    }
+
+    pub fn wall_filter(mut self, filter: CollisionFilter) -> Self {
+        self.wall_filter = filter;
+        self
+    }
+
+    pub fn build(self) -> Box<T> {
+        let mut walls = Vec::new();
+        if let Some((left, bottom, right, top)) = self.bounds {
+            walls.push(Wall::new(self.wall_material, left, Orientation::Left));
+            walls.push(Wall::new(self.wall_material, top, Orientation::Top));
+            walls.push(Wall::new(self.wall_material, right, Orientation::Right));
+            walls.push(Wall::new(self.wall_material, bottom, Orientation::Bottom));
+            for wall in &mut walls {
+                wall.filter = self.wall_filter;
+            }
+        }
+        walls.extend(self.walls);
+
+        Box {
+            walls,
+            gravity: self.gravity,
+            integrator: self.integrator,
+            force_field: self.force_field,
+            bodies: BodySet::new(),
+            proxies: BodySet::new(),
+            broad_phase: (self.broad_phase)(&self.solver_config),
+            fixture_overlaps: HashMap::new(),
+            overlaps: HashMap::new(),
+            began_overlaps: Vec::new(),
+            ended_overlaps: Vec::new(),
+            solver: ContactSolver::new(self.solver_config),
+            step_config: self.step_config,
+            accumulator: 0.0,
+            previous_transforms: HashMap::new(),
+        }
     }
 }
 
-fn drive_particle(particle: &mut RigidBody, duration: Float) {
-    particle.position = particle.position
-        + particle.velocity * duration
-        + particle.acceleration * (duration * duration) / 2.0;
-    particle.velocity = particle.velocity + particle.acceleration * duration;
+impl<T> Default for BoxBuilder<T>
+where
+    T: Collider + Bounded + MassLike,
+{
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -1,121 +1,503 @@
 use crate::{
-    algebra::{min, Float, Vec2},
-    detection_narrow_phase::detect_collision_circle_and_circle,
-    detection_narrow_phase::CollisionInfo,
-    shapes::{Bounded, Circle, MaterialLike, Orientation, Rectangle, RigidBodyLike, Wall},
+    algebra::{min, Float, Mat22, Vec2},
+    contact_solver::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_capsule_and_capsule, detect_collision_capsule_and_polygon,
+        detect_collision_capsule_and_rectangle, detect_collision_circle_and_capsule,
+        detect_collision_circle_and_circle, detect_collision_circle_and_polygon,
+        detect_collision_circle_and_rectangle, detect_collision_polygon_and_polygon,
+        detect_collision_rectangle_and_polygon, detect_collision_rectangle_and_rectangle,
+        detect_collision_segment_and_capsule, detect_collision_segment_and_circle,
+        detect_collision_segment_and_polygon, detect_collision_segment_and_rectangle,
+        detect_collision_wall_and_capsule, detect_collision_wall_and_circle,
+        detect_collision_wall_and_polygon, detect_collision_wall_and_rectangle, CollisionInfo,
+    },
+    shapes::{
+        BodyType, Capsule, Circle, Material, MaterialLike, Polygon, Rectangle, RigidBody,
+        RigidBodyLike, Segment, Wall,
+    },
 };
 
-const POSITION_SLOT: Float = 0.01;
-const POSITION_CORRECTION_FACTOR: Float = 0.2;
+pub fn collide_circle_and_circle(
+    circle1: &mut Circle,
+    circle2: &mut Circle,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_circle_and_circle(circle1, circle2, config) {
+        let material = mix_materials(circle1.material(), circle2.material());
+        resolve_collision(
+            circle1.rigid_body_mut(),
+            circle2.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
 
-pub fn collide_circle_and_circle(circle1: &mut Circle, circle2: &mut Circle) {
-    if let Some(CollisionInfo {
-        penetration,
-        normal,
-    }) = detect_collision_circle_and_circle(circle1, circle2)
-    {
-        let restitution = min(
-            circle1.material().restitution,
-            circle2.material().restitution,
+pub fn collide_circle_and_rectangle(
+    circle: &mut Circle,
+    rect: &mut Rectangle,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_circle_and_rectangle(circle, rect, config) {
+        let material = mix_materials(circle.material(), rect.material());
+        resolve_collision(
+            circle.rigid_body_mut(),
+            rect.rigid_body_mut(),
+            material,
+            info,
+            config,
         );
-        let p1 = circle1.rigid_body_mut();
-        let p2 = circle2.rigid_body_mut();
+    }
+}
 
-        let rel_vel = p2.velocity - p1.velocity;
-        let rel_vel_along_normal = rel_vel * normal;
+pub fn collide_rectangle_and_rectange(
+    rect1: &mut Rectangle,
+    rect2: &mut Rectangle,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_rectangle_and_rectangle(rect1, rect2, config) {
+        let material = mix_materials(rect1.material(), rect2.material());
+        resolve_collision(
+            rect1.rigid_body_mut(),
+            rect2.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
 
-        if rel_vel_along_normal > 0.0 {
-            return;
-        }
+pub fn collide_circle_and_polygon(
+    circle: &mut Circle,
+    polygon: &mut Polygon,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_circle_and_polygon(circle, polygon, config) {
+        let material = mix_materials(circle.material(), polygon.material());
+        resolve_collision(
+            circle.rigid_body_mut(),
+            polygon.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
 
-        let impulse_scalar =
-            -(1.0 + restitution) * rel_vel_along_normal / (p1.inverse_mass() + p2.inverse_mass());
-        let impulse = normal * impulse_scalar;
+pub fn collide_rectangle_and_polygon(
+    rect: &mut Rectangle,
+    polygon: &mut Polygon,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_rectangle_and_polygon(rect, polygon, config) {
+        let material = mix_materials(rect.material(), polygon.material());
+        resolve_collision(
+            rect.rigid_body_mut(),
+            polygon.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
 
-        p1.velocity = p1.velocity - (impulse * p1.inverse_mass());
-        p2.velocity = p2.velocity + (impulse * p2.inverse_mass());
+pub fn collide_polygon_and_polygon(
+    polygon1: &mut Polygon,
+    polygon2: &mut Polygon,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_polygon_and_polygon(polygon1, polygon2, config) {
+        let material = mix_materials(polygon1.material(), polygon2.material());
+        resolve_collision(
+            polygon1.rigid_body_mut(),
+            polygon2.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
 
-        if penetration > POSITION_SLOT {
-            let correction = normal
-                * (penetration / (p1.inverse_mass() + p2.inverse_mass()))
-                * POSITION_CORRECTION_FACTOR;
+pub fn collide_circle_and_capsule(
+    circle: &mut Circle,
+    capsule: &mut Capsule,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_circle_and_capsule(circle, capsule, config) {
+        let material = mix_materials(circle.material(), capsule.material());
+        resolve_collision(
+            circle.rigid_body_mut(),
+            capsule.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
 
-            p1.position = p1.position - correction * p1.inverse_mass();
-            p2.position = p2.position + correction * p2.inverse_mass();
-        }
+pub fn collide_capsule_and_capsule(
+    capsule1: &mut Capsule,
+    capsule2: &mut Capsule,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_capsule_and_capsule(capsule1, capsule2, config) {
+        let material = mix_materials(capsule1.material(), capsule2.material());
+        resolve_collision(
+            capsule1.rigid_body_mut(),
+            capsule2.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
     }
 }
 
-pub fn collide_circle_and_rectangle(_circle: &mut Circle, _rect: &mut Rectangle) {
-    todo!()
+pub fn collide_capsule_and_rectangle(
+    capsule: &mut Capsule,
+    rect: &mut Rectangle,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_capsule_and_rectangle(capsule, rect, config) {
+        let material = mix_materials(capsule.material(), rect.material());
+        resolve_collision(
+            capsule.rigid_body_mut(),
+            rect.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
 }
 
-pub fn collide_rectangle_and_rectange(_rect1: &mut Rectangle, _rect2: &mut Rectangle) {
-    todo!()
+pub fn collide_capsule_and_polygon(
+    capsule: &mut Capsule,
+    polygon: &mut Polygon,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_capsule_and_polygon(capsule, polygon, config) {
+        let material = mix_materials(capsule.material(), polygon.material());
+        resolve_collision(
+            capsule.rigid_body_mut(),
+            polygon.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
 }
 
-pub fn collide_wall_and_circle(wall: &Wall, circle: &mut Circle) {
-    let restitution = min(circle.material().restitution, wall.material().restitution);
-    match wall.orientation {
-        Orientation::Left => {
-            if circle.bound_left() < wall.bound {
-                let r = circle.radius;
-                let mut p = circle.rigid_body_mut();
-                p.position = Vec2 {
-                    x: wall.bound + r,
-                    y: p.position.y,
-                };
-                p.velocity = Vec2 {
-                    x: -restitution * p.velocity.x,
-                    y: p.velocity.y,
-                };
-            }
-        }
-        Orientation::Top => {
-            if circle.bound_top() > wall.bound {
-                let r = circle.radius;
-                let mut p = circle.rigid_body_mut();
-                p.position = Vec2 {
-                    x: p.position.x,
-                    y: wall.bound - r,
-                };
-                p.velocity = Vec2 {
-                    x: p.velocity.x,
-                    y: -restitution * p.velocity.y,
-                };
+pub fn collide_segment_and_circle(
+    segment: &mut Segment,
+    circle: &mut Circle,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_segment_and_circle(segment, circle, config) {
+        let material = mix_materials(segment.material(), circle.material());
+        resolve_collision(
+            segment.rigid_body_mut(),
+            circle.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
+
+pub fn collide_segment_and_rectangle(
+    segment: &mut Segment,
+    rect: &mut Rectangle,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_segment_and_rectangle(segment, rect, config) {
+        let material = mix_materials(segment.material(), rect.material());
+        resolve_collision(
+            segment.rigid_body_mut(),
+            rect.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
+
+pub fn collide_segment_and_polygon(
+    segment: &mut Segment,
+    polygon: &mut Polygon,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_segment_and_polygon(segment, polygon, config) {
+        let material = mix_materials(segment.material(), polygon.material());
+        resolve_collision(
+            segment.rigid_body_mut(),
+            polygon.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
+
+pub fn collide_segment_and_capsule(
+    segment: &mut Segment,
+    capsule: &mut Capsule,
+    config: &SolverConfig,
+) {
+    if let Some(info) = detect_collision_segment_and_capsule(segment, capsule, config) {
+        let material = mix_materials(segment.material(), capsule.material());
+        resolve_collision(
+            segment.rigid_body_mut(),
+            capsule.rigid_body_mut(),
+            material,
+            info,
+            config,
+        );
+    }
+}
+
+pub fn collide_wall_and_circle(wall: &Wall, circle: &mut Circle, config: &SolverConfig) {
+    if let Some(info) = detect_collision_wall_and_circle(wall, circle, config) {
+        let material = mix_materials(circle.material(), wall.material());
+        resolve_wall_collision(circle.rigid_body_mut(), material, info, config);
+    }
+}
+
+pub fn collide_wall_and_rectangle(wall: &Wall, rect: &mut Rectangle, config: &SolverConfig) {
+    if let Some(info) = detect_collision_wall_and_rectangle(wall, rect, config) {
+        let material = mix_materials(rect.material(), wall.material());
+        resolve_wall_collision(rect.rigid_body_mut(), material, info, config);
+    }
+}
+
+pub fn collide_wall_and_polygon(wall: &Wall, polygon: &mut Polygon, config: &SolverConfig) {
+    if let Some(info) = detect_collision_wall_and_polygon(wall, polygon, config) {
+        let material = mix_materials(polygon.material(), wall.material());
+        resolve_wall_collision(polygon.rigid_body_mut(), material, info, config);
+    }
+}
+
+pub fn collide_wall_and_capsule(wall: &Wall, capsule: &mut Capsule, config: &SolverConfig) {
+    if let Some(info) = detect_collision_wall_and_capsule(wall, capsule, config) {
+        let material = mix_materials(capsule.material(), wall.material());
+        resolve_wall_collision(capsule.rigid_body_mut(), material, info, config);
+    }
+}
+
+// 恢复系数取两者中较小者，摩擦系数取两者的几何平均。
+pub(crate) fn mix_materials(material1: &Material, material2: &Material) -> Material {
+    Material {
+        restitution: min(material1.restitution, material2.restitution),
+        static_friction: (material1.static_friction * material2.static_friction).sqrt(),
+        dynamic_friction: (material1.dynamic_friction * material2.dynamic_friction).sqrt(),
+        ..Default::default()
+    }
+}
+
+fn resolve_collision(
+    p1: &mut RigidBody,
+    p2: &mut RigidBody,
+    material: Material,
+    CollisionInfo {
+        penetration,
+        normal,
+        contacts,
+    }: CollisionInfo,
+    config: &SolverConfig,
+) {
+    if p1.is_immovable() && p2.is_immovable() {
+        return;
+    }
+
+    let contacts: Vec<Vec2> = contacts.iter().map(|contact| contact.point).collect();
+    let normal_impulses = apply_contact_impulses(p1, p2, material, normal, &contacts, config);
+    if normal_impulses.iter().all(|&impulse| impulse <= 0.0) {
+        return;
+    }
+    apply_friction_impulses(
+        p1,
+        p2,
+        material,
+        normal,
+        &contacts,
+        &normal_impulses,
+        config,
+    );
+
+    let inverse_mass_sum = p1.inverse_mass() + p2.inverse_mass();
+    if penetration > config.linear_slop && inverse_mass_sum > 0.0 {
+        let correction = normal * (penetration / inverse_mass_sum) * config.baumgarte;
+
+        p1.position -= correction * p1.inverse_mass();
+        p2.position += correction * p2.inverse_mass();
+    }
+}
+
+// 墙体视为质量无穷大的静态刚体，穿模与物体之间相同，超出线性容差的部分按比例修正；
+// 墙体不影响静态与运动学物体。
+fn resolve_wall_collision(
+    p: &mut RigidBody,
+    material: Material,
+    CollisionInfo {
+        penetration,
+        normal,
+        contacts,
+    }: CollisionInfo,
+    config: &SolverConfig,
+) {
+    if !p.is_dynamic() {
+        return;
+    }
+
+    let contacts: Vec<Vec2> = contacts.iter().map(|contact| contact.point).collect();
+    let mut wall_body = RigidBody {
+        body_type: BodyType::Static,
+        ..Default::default()
+    };
+    let normal_impulses =
+        apply_contact_impulses(&mut wall_body, p, material, normal, &contacts, config);
+    apply_friction_impulses(
+        &mut wall_body,
+        p,
+        material,
+        normal,
+        &contacts,
+        &normal_impulses,
+        config,
+    );
+
+    let inverse_mass = p.inverse_mass();
+    if penetration > config.linear_slop && inverse_mass > 0.0 {
+        let correction = normal * (penetration / inverse_mass) * config.baumgarte;
+        p.position += correction * inverse_mass;
+    }
+}
+
+// 对各接触点同时施加法向冲量，并返回各接触点上的冲量大小。
+// 法向相对速度低于恢复速度阈值时不再反弹，仅消除相互靠近的速度。
+fn apply_contact_impulses(
+    p1: &mut RigidBody,
+    p2: &mut RigidBody,
+    material: Material,
+    normal: Vec2,
+    contacts: &[Vec2],
+    config: &SolverConfig,
+) -> Vec<Float> {
+    let arms: Vec<(Float, Float)> = contacts
+        .iter()
+        .map(|&contact| {
+            (
+                (contact - p1.position).cross(normal),
+                (contact - p2.position).cross(normal),
+            )
+        })
+        .collect();
+    let k = |i: usize, j: usize| {
+        p1.inverse_mass()
+            + p2.inverse_mass()
+            + p1.inverse_inertia() * arms[i].0 * arms[j].0
+            + p2.inverse_inertia() * arms[i].1 * arms[j].1
+    };
+    let bias: Vec<Float> = contacts
+        .iter()
+        .map(|&contact| {
+            let rel_vel_along_normal = (p2.velocity_at(contact) - p1.velocity_at(contact)) * normal;
+            if rel_vel_along_normal < -config.restitution_velocity_threshold {
+                (1.0 + material.restitution) * rel_vel_along_normal
+            } else {
+                rel_vel_along_normal
             }
+        })
+        .collect();
+
+    let impulses = match contacts.len() {
+        1 => vec![(-bias[0] * inverse_or_zero(k(0, 0))).max(0.0)],
+        2 => solve_block_impulses(
+            Mat22::new(k(0, 0), k(0, 1), k(1, 0), k(1, 1)),
+            Vec2::new(bias[0], bias[1]),
+            config,
+        ),
+        _ => unreachable!("a contact manifold holds at most two points"),
+    };
+
+    for (&contact, &impulse_scalar) in contacts.iter().zip(&impulses) {
+        let impulse = normal * impulse_scalar;
+        p1.apply_impulse(-impulse, contact);
+        p2.apply_impulse(impulse, contact);
+    }
+
+    impulses
+}
+
+// 库仑摩擦：切向冲量若能使接触点相对静止且不超过静摩擦上限，则直接施加；
+// 否则接触点发生滑动，切向冲量大小为动摩擦系数乘以法向冲量。
+fn apply_friction_impulses(
+    p1: &mut RigidBody,
+    p2: &mut RigidBody,
+    material: Material,
+    normal: Vec2,
+    contacts: &[Vec2],
+    normal_impulses: &[Float],
+    config: &SolverConfig,
+) {
+    for (&contact, &normal_impulse) in contacts.iter().zip(normal_impulses) {
+        if normal_impulse <= 0.0 {
+            continue;
         }
-        Orientation::Right => {
-            if circle.bound_right() > wall.bound {
-                let r = circle.radius;
-                let mut p = circle.rigid_body_mut();
-                p.position = Vec2 {
-                    x: wall.bound - r,
-                    y: p.position.y,
-                };
-                p.velocity = Vec2 {
-                    x: -restitution * p.velocity.x,
-                    y: p.velocity.y,
-                };
-            }
+
+        let rel_vel = p2.velocity_at(contact) - p1.velocity_at(contact);
+        let tangent_vel = rel_vel - normal * (rel_vel * normal);
+        let tangent_speed = tangent_vel.length();
+        if tangent_speed < config.float_tolerance {
+            continue;
         }
-        Orientation::Bottom => {
-            if circle.bound_bottom() < wall.bound {
-                let r = circle.radius;
-                let mut p = circle.rigid_body_mut();
-                p.position = Vec2 {
-                    x: p.position.x,
-                    y: wall.bound + r,
-                };
-                p.velocity = Vec2 {
-                    x: p.velocity.x,
-                    y: -restitution * p.velocity.y,
-                };
-            }
+        let tangent = tangent_vel / tangent_speed;
+
+        let arm1 = (contact - p1.position).cross(tangent);
+        let arm2 = (contact - p2.position).cross(tangent);
+        let k = p1.inverse_mass()
+            + p2.inverse_mass()
+            + p1.inverse_inertia() * arm1 * arm1
+            + p2.inverse_inertia() * arm2 * arm2;
+        let stick_impulse = -(rel_vel * tangent) * inverse_or_zero(k);
+        let friction_impulse = if stick_impulse.abs() <= material.static_friction * normal_impulse {
+            stick_impulse
+        } else {
+            -material.dynamic_friction * normal_impulse
+        };
+
+        let impulse = tangent * friction_impulse;
+        p1.apply_impulse(-impulse, contact);
+        p2.apply_impulse(impulse, contact);
+    }
+}
+
+// 两个接触点需同时求解：寻找 λ >= 0，使得 Kλ + b >= 0 且两者互补，枚举所有情况即可。
+fn solve_block_impulses(k: Mat22, bias: Vec2, config: &SolverConfig) -> Vec<Float> {
+    if let Some(impulses) = k.solve_with_tolerance(-bias, config.float_tolerance) {
+        if impulses.x >= 0.0 && impulses.y >= 0.0 {
+            return vec![impulses.x, impulses.y];
         }
     }
+
+    let impulse1 = -bias.x * inverse_or_zero(k.m00);
+    if impulse1 >= 0.0 && k.m10 * impulse1 + bias.y >= 0.0 {
+        return vec![impulse1, 0.0];
+    }
+
+    let impulse2 = -bias.y * inverse_or_zero(k.m11);
+    if impulse2 >= 0.0 && k.m01 * impulse2 + bias.x >= 0.0 {
+        return vec![0.0, impulse2];
+    }
+
+    vec![0.0, 0.0]
 }
 
-pub fn collide_wall_and_rectangle(_wall: &Wall, _rect: &mut Rectangle) {
-    todo!()
+// 有效质量的倒数；双方都无法被冲量推动时 k 为 0，此时不施加冲量。
+pub(crate) fn inverse_or_zero(k: Float) -> Float {
+    if k > 0.0 {
+        1.0 / k
+    } else {
+        0.0
+    }
 }
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use crate::{
+    algebra::{Float, Vec2, FLOADT_TOLERANCE},
+    body_set::BodyHandle,
+    collide_calculation::{inverse_or_zero, mix_materials},
+    detection_narrow_phase::{CollisionInfo, ContactFeature},
+    shapes::{BodyType, Material, RigidBody, RigidBodyLike},
+};
+
+// 各项容差均与长度单位相关，以像素与以米为单位的场景应分别设置。
+// linear_slop：允许的穿透深度，小于该值时不做位置修正，以免物体相互接触时来回抖动。
+// baumgarte：每次位置迭代修正剩余穿透深度的比例。
+// restitution_velocity_threshold：法向相对速度低于该值时不再反弹，使物体能够静止下来。
+// aabb_margin：动态树中包围盒向外放大的距离，物体在放大的范围内移动时无需更新树结构。
+#[derive(Debug, Clone, Copy)]
+pub struct SolverConfig {
+    pub linear_slop: Float,
+    pub baumgarte: Float,
+    pub restitution_velocity_threshold: Float,
+    pub velocity_iterations: usize,
+    pub position_iterations: usize,
+    pub float_tolerance: Float,
+    pub aabb_margin: Float,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            linear_slop: 0.01,
+            baumgarte: 0.2,
+            restitution_velocity_threshold: 0.0,
+            velocity_iterations: 10,
+            position_iterations: 3,
+            float_tolerance: FLOADT_TOLERANCE,
+            aabb_margin: 0.1,
+        }
+    }
+}
+
+// 物体句柄与其夹具的下标。
+pub(crate) type FixtureKey = (BodyHandle, usize);
+
+// 接触的一方：另一个物体的夹具，或下标对应的墙体。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ContactPartner {
+    Body(FixtureKey),
+    Wall(usize),
+}
+
+type ContactKey = (ContactPartner, FixtureKey, ContactFeature);
+
+struct ContactPoint {
+    feature: ContactFeature,
+    point: Vec2,
+    normal_mass: Float,
+    tangent_mass: Float,
+    velocity_bias: Float,
+    normal_impulse: Float,
+    tangent_impulse: Float,
+}
+
+// 法线由 body1 指向 body2，body1 为 None 时表示墙体。
+struct ContactManifold {
+    body1: Option<usize>,
+    body2: usize,
+    partner: ContactPartner,
+    fixture: FixtureKey,
+    material: Material,
+    normal: Vec2,
+    penetration: Float,
+    // 检测到碰撞时两者的位置，用于在位置迭代中估算剩余的穿透深度。
+    position1: Vec2,
+    position2: Vec2,
+    points: Vec<ContactPoint>,
+}
+
+// 先收集一帧内的所有接触，再统一迭代求解：每个接触点累积冲量并加以约束，
+// 下一帧按特征编号找回同一接触点的冲量作为初值（热启动）。
+pub(crate) struct ContactSolver {
+    pub config: SolverConfig,
+    manifolds: Vec<ContactManifold>,
+    impulses: HashMap<ContactKey, (Float, Float)>,
+}
+
+impl ContactSolver {
+    pub fn new(config: SolverConfig) -> Self {
+        Self {
+            config,
+            manifolds: Vec::new(),
+            impulses: HashMap::new(),
+        }
+    }
+
+    pub fn add_body_contact(
+        &mut self,
+        (index1, fixture1, material1): (usize, FixtureKey, &Material),
+        (index2, fixture2, material2): (usize, FixtureKey, &Material),
+        info: CollisionInfo,
+    ) {
+        self.add_manifold(
+            Some(index1),
+            index2,
+            ContactPartner::Body(fixture1),
+            fixture2,
+            mix_materials(material1, material2),
+            info,
+        );
+    }
+
+    pub fn add_wall_contact(
+        &mut self,
+        (wall_index, wall_material): (usize, &Material),
+        (index, fixture, material): (usize, FixtureKey, &Material),
+        info: CollisionInfo,
+    ) {
+        self.add_manifold(
+            None,
+            index,
+            ContactPartner::Wall(wall_index),
+            fixture,
+            mix_materials(material, wall_material),
+            info,
+        );
+    }
+
+    pub fn solve<T: RigidBodyLike>(&mut self, bodies: &mut [T]) {
+        let mut manifolds = std::mem::take(&mut self.manifolds);
+
+        for manifold in &mut manifolds {
+            self.prepare(bodies, manifold);
+        }
+        for manifold in &manifolds {
+            warm_start(bodies, manifold);
+        }
+        for _ in 0..self.config.velocity_iterations {
+            for manifold in &mut manifolds {
+                solve_velocity(bodies, manifold);
+            }
+        }
+        for _ in 0..self.config.position_iterations {
+            for manifold in &manifolds {
+                correct_position(bodies, manifold, &self.config);
+            }
+        }
+
+        self.impulses.clear();
+        for manifold in &manifolds {
+            for point in &manifold.points {
+                self.impulses.insert(
+                    (manifold.partner, manifold.fixture, point.feature),
+                    (point.normal_impulse, point.tangent_impulse),
+                );
+            }
+        }
+    }
+
+    fn add_manifold(
+        &mut self,
+        body1: Option<usize>,
+        body2: usize,
+        partner: ContactPartner,
+        fixture: FixtureKey,
+        material: Material,
+        info: CollisionInfo,
+    ) {
+        let points = info
+            .contacts
+            .iter()
+            .map(|contact| ContactPoint {
+                feature: contact.feature,
+                point: contact.point,
+                normal_mass: 0.0,
+                tangent_mass: 0.0,
+                velocity_bias: 0.0,
+                normal_impulse: 0.0,
+                tangent_impulse: 0.0,
+            })
+            .collect();
+
+        self.manifolds.push(ContactManifold {
+            body1,
+            body2,
+            partner,
+            fixture,
+            material,
+            normal: info.normal,
+            penetration: info.penetration,
+            position1: Vec2::default(),
+            position2: Vec2::default(),
+            points,
+        });
+    }
+
+    // 计算各接触点的有效质量与恢复速度，并取回上一帧的累积冲量。
+    fn prepare<T: RigidBodyLike>(&self, bodies: &[T], manifold: &mut ContactManifold) {
+        let p1 = get_body(bodies, manifold.body1);
+        let p2 = *bodies[manifold.body2].rigid_body();
+        let normal = manifold.normal;
+        let tangent = get_tangent(normal);
+        manifold.position1 = p1.position;
+        manifold.position2 = p2.position;
+
+        for point in &mut manifold.points {
+            point.normal_mass = inverse_or_zero(effective_mass(&p1, &p2, point.point, normal));
+            point.tangent_mass = inverse_or_zero(effective_mass(&p1, &p2, point.point, tangent));
+
+            let rel_vel_along_normal =
+                (p2.velocity_at(point.point) - p1.velocity_at(point.point)) * normal;
+            point.velocity_bias =
+                if rel_vel_along_normal < -self.config.restitution_velocity_threshold {
+                    -manifold.material.restitution * rel_vel_along_normal
+                } else {
+                    0.0
+                };
+
+            let key = (manifold.partner, manifold.fixture, point.feature);
+            if let Some(&(normal_impulse, tangent_impulse)) = self.impulses.get(&key) {
+                point.normal_impulse = normal_impulse;
+                point.tangent_impulse = tangent_impulse;
+            }
+        }
+    }
+}
+
+fn warm_start<T: RigidBodyLike>(bodies: &mut [T], manifold: &ContactManifold) {
+    let mut p1 = get_body(bodies, manifold.body1);
+    let mut p2 = *bodies[manifold.body2].rigid_body();
+    let tangent = get_tangent(manifold.normal);
+
+    for point in &manifold.points {
+        let impulse = manifold.normal * point.normal_impulse + tangent * point.tangent_impulse;
+        p1.apply_impulse(-impulse, point.point);
+        p2.apply_impulse(impulse, point.point);
+    }
+
+    set_body(bodies, manifold.body1, &p1);
+    set_body(bodies, Some(manifold.body2), &p2);
+}
+
+// 先求解摩擦，再求解法向冲量：累积的法向冲量不得为负，
+// 累积的切向冲量不超过静摩擦上限，超出时按动摩擦滑动。
+fn solve_velocity<T: RigidBodyLike>(bodies: &mut [T], manifold: &mut ContactManifold) {
+    let mut p1 = get_body(bodies, manifold.body1);
+    let mut p2 = *bodies[manifold.body2].rigid_body();
+    let normal = manifold.normal;
+    let tangent = get_tangent(normal);
+    let material = manifold.material;
+
+    for point in &mut manifold.points {
+        let rel_vel = p2.velocity_at(point.point) - p1.velocity_at(point.point);
+        let stick_impulse = point.tangent_impulse - point.tangent_mass * (rel_vel * tangent);
+        let new_impulse = if stick_impulse.abs() <= material.static_friction * point.normal_impulse
+        {
+            stick_impulse
+        } else {
+            stick_impulse.signum() * material.dynamic_friction * point.normal_impulse
+        };
+        let impulse = tangent * (new_impulse - point.tangent_impulse);
+        point.tangent_impulse = new_impulse;
+        p1.apply_impulse(-impulse, point.point);
+        p2.apply_impulse(impulse, point.point);
+    }
+
+    for point in &mut manifold.points {
+        let rel_vel = p2.velocity_at(point.point) - p1.velocity_at(point.point);
+        let lambda = -point.normal_mass * (rel_vel * normal - point.velocity_bias);
+        let new_impulse = (point.normal_impulse + lambda).max(0.0);
+        let impulse = normal * (new_impulse - point.normal_impulse);
+        point.normal_impulse = new_impulse;
+        p1.apply_impulse(-impulse, point.point);
+        p2.apply_impulse(impulse, point.point);
+    }
+
+    set_body(bodies, manifold.body1, &p1);
+    set_body(bodies, Some(manifold.body2), &p2);
+}
+
+// 每次迭代按比例修正超出线性容差的穿模；墙体视为质量无穷大的静态刚体，只移动另一侧的物体。
+fn correct_position<T: RigidBodyLike>(
+    bodies: &mut [T],
+    manifold: &ContactManifold,
+    config: &SolverConfig,
+) {
+    let normal = manifold.normal;
+    let p1 = get_body(bodies, manifold.body1);
+    let p2 = bodies[manifold.body2].rigid_body();
+    let displacement = (p2.position - manifold.position2) - (p1.position - manifold.position1);
+    let penetration = manifold.penetration - displacement * normal;
+
+    let inverse_mass1 = p1.inverse_mass();
+    let inverse_mass2 = p2.inverse_mass();
+    if penetration > config.linear_slop && inverse_mass1 + inverse_mass2 > 0.0 {
+        let correction =
+            normal * (penetration / (inverse_mass1 + inverse_mass2)) * config.baumgarte;
+
+        if let Some(index1) = manifold.body1 {
+            bodies[index1].rigid_body_mut().position -= correction * inverse_mass1;
+        }
+        bodies[manifold.body2].rigid_body_mut().position += correction * inverse_mass2;
+    }
+}
+
+fn effective_mass(p1: &RigidBody, p2: &RigidBody, point: Vec2, direction: Vec2) -> Float {
+    let arm1 = (point - p1.position).cross(direction);
+    let arm2 = (point - p2.position).cross(direction);
+    p1.inverse_mass()
+        + p2.inverse_mass()
+        + p1.inverse_inertia() * arm1 * arm1
+        + p2.inverse_inertia() * arm2 * arm2
+}
+
+fn get_tangent(normal: Vec2) -> Vec2 {
+    -normal.perp()
+}
+
+// 墙体视为静态刚体。
+fn get_body<T: RigidBodyLike>(bodies: &[T], index: Option<usize>) -> RigidBody {
+    match index {
+        Some(index) => *bodies[index].rigid_body(),
+        None => RigidBody {
+            body_type: BodyType::Static,
+            ..Default::default()
+        },
+    }
+}
+
+fn set_body<T: RigidBodyLike>(bodies: &mut [T], index: Option<usize>, body: &RigidBody) {
+    if let Some(index) = index {
+        let target = bodies[index].rigid_body_mut();
+        target.velocity = body.velocity;
+        target.angular_velocity = body.angular_velocity;
+    }
+}
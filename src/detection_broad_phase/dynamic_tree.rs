@@ -0,0 +1,364 @@
+use std::collections::HashSet;
+
+use super::{Aabb, BroadPhase, PairTracker};
+use crate::{algebra::Float, body_set::BodyHandle, contact_solver::SolverConfig};
+
+struct Node {
+    // 叶子节点存放放大后的包围盒，内部节点存放子节点包围盒的并集。
+    aabb: Aabb,
+    parent: Option<usize>,
+    children: Option<(usize, usize)>,
+    height: usize,
+    // 叶子节点对应物体的槽位。
+    slot: usize,
+}
+
+struct Proxy {
+    handle: BodyHandle,
+    aabb: Aabb,
+    leaf: usize,
+}
+
+// 动态包围盒树：叶子节点的包围盒向外放大 margin，物体在放大的范围内移动时树结构保持不变，
+// 移出后才重新插入。插入时按周长代价选择兄弟节点，并沿路径向上旋转保持平衡。
+// 放大后的包围盒相交的物体对作为候选，只有新插入或重新插入的物体需要查询树来更新候选；
+// 候选中实际的包围盒相交的才是结果。
+pub struct DynamicTree {
+    margin: Float,
+    nodes: Vec<Node>,
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+    proxies: Vec<Option<Proxy>>,
+    moved: Vec<BodyHandle>,
+    candidates: HashSet<(BodyHandle, BodyHandle)>,
+    pairs: PairTracker,
+}
+
+impl DynamicTree {
+    pub fn with_margin(margin: Float) -> Self {
+        Self {
+            margin,
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            root: None,
+            proxies: Vec::new(),
+            moved: Vec::new(),
+            candidates: HashSet::new(),
+            pairs: PairTracker::new(),
+        }
+    }
+
+    // 只有一个叶子时高度为 0。
+    pub fn height(&self) -> usize {
+        self.root.map_or(0, |root| self.nodes[root].height)
+    }
+
+    fn allocate_node(&mut self, node: Node) -> usize {
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let root = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(leaf);
+                self.nodes[leaf].parent = None;
+                return;
+            }
+        };
+
+        // 沿代价较小的一侧向下，直到以当前节点为兄弟节点的代价不高于继续向下。
+        let leaf_aabb = self.nodes[leaf].aabb;
+        let mut index = root;
+        while let Some((child1, child2)) = self.nodes[index].children {
+            let perimeter = self.nodes[index].aabb.perimeter();
+            let combined_perimeter = self.nodes[index].aabb.union(&leaf_aabb).perimeter();
+            let cost = 2.0 * combined_perimeter;
+            let inheritance_cost = 2.0 * (combined_perimeter - perimeter);
+            let child_cost = |child: usize| {
+                let node = &self.nodes[child];
+                let perimeter = node.aabb.union(&leaf_aabb).perimeter();
+                match node.children {
+                    None => perimeter + inheritance_cost,
+                    Some(_) => perimeter - node.aabb.perimeter() + inheritance_cost,
+                }
+            };
+            let (cost1, cost2) = (child_cost(child1), child_cost(child2));
+
+            if cost < cost1 && cost < cost2 {
+                break;
+            }
+            index = if cost1 < cost2 { child1 } else { child2 };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate_node(Node {
+            aabb: self.nodes[sibling].aabb.union(&leaf_aabb),
+            parent: old_parent,
+            children: Some((sibling, leaf)),
+            height: self.nodes[sibling].height + 1,
+            slot: 0,
+        });
+        match old_parent {
+            Some(old_parent) => self.replace_child(old_parent, sibling, new_parent),
+            None => self.root = Some(new_parent),
+        }
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+
+        self.refit(self.nodes[leaf].parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        let parent = match self.nodes[leaf].parent {
+            Some(parent) => parent,
+            None => {
+                self.root = None;
+                return;
+            }
+        };
+        let sibling = match self.nodes[parent].children {
+            Some((child1, child2)) if child1 == leaf => child2,
+            Some((child1, _)) => child1,
+            None => unreachable!(),
+        };
+
+        let grandparent = self.nodes[parent].parent;
+        match grandparent {
+            Some(grandparent) => self.replace_child(grandparent, parent, sibling),
+            None => self.root = Some(sibling),
+        }
+        self.nodes[sibling].parent = grandparent;
+        self.nodes[leaf].parent = None;
+        self.free_nodes.push(parent);
+
+        self.refit(grandparent);
+    }
+
+    fn replace_child(&mut self, parent: usize, old_child: usize, new_child: usize) {
+        if let Some((child1, child2)) = &mut self.nodes[parent].children {
+            if *child1 == old_child {
+                *child1 = new_child;
+            } else {
+                *child2 = new_child;
+            }
+        }
+    }
+
+    // 自下而上重新计算包围盒与高度，途中逐个节点做平衡。
+    fn refit(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            let current = self.balance(current);
+            self.update_node(current);
+            index = self.nodes[current].parent;
+        }
+    }
+
+    fn update_node(&mut self, index: usize) {
+        if let Some((child1, child2)) = self.nodes[index].children {
+            let (node1, node2) = (&self.nodes[child1], &self.nodes[child2]);
+            let aabb = node1.aabb.union(&node2.aabb);
+            let height = node1.height.max(node2.height) + 1;
+            self.nodes[index].aabb = aabb;
+            self.nodes[index].height = height;
+        }
+    }
+
+    // 两棵子树高度差超过 1 时，将较高的子节点旋转到当前位置，返回旋转后子树的根。
+    fn balance(&mut self, index: usize) -> usize {
+        let (child1, child2) = match self.nodes[index].children {
+            Some(children) if self.nodes[index].height >= 2 => children,
+            _ => return index,
+        };
+
+        let (height1, height2) = (self.nodes[child1].height, self.nodes[child2].height);
+        if height2 > height1 + 1 {
+            self.rotate(index, child2, child1)
+        } else if height1 > height2 + 1 {
+            self.rotate(index, child1, child2)
+        } else {
+            index
+        }
+    }
+
+    // 将子节点 up 提升到 index 的位置：up 较高的子树留在 up 下，较矮的子树与 other 一起挂在 index 下。
+    fn rotate(&mut self, index: usize, up: usize, other: usize) -> usize {
+        let (grandchild1, grandchild2) = self.nodes[up].children.unwrap();
+
+        let parent = self.nodes[index].parent;
+        self.nodes[up].parent = parent;
+        self.nodes[index].parent = Some(up);
+        match parent {
+            Some(parent) => self.replace_child(parent, index, up),
+            None => self.root = Some(up),
+        }
+
+        let (taller, shorter) = if self.nodes[grandchild1].height > self.nodes[grandchild2].height {
+            (grandchild1, grandchild2)
+        } else {
+            (grandchild2, grandchild1)
+        };
+        self.nodes[up].children = Some((index, taller));
+        self.nodes[index].children = Some((other, shorter));
+        self.nodes[shorter].parent = Some(index);
+
+        self.update_node(index);
+        self.update_node(up);
+        up
+    }
+
+    // 遍历放大后的包围盒与 region 相交的叶子节点，传入其槽位。
+    fn for_each_leaf(&self, region: &Aabb, mut callback: impl FnMut(usize)) {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.aabb.overlaps(region) {
+                continue;
+            }
+            match node.children {
+                Some((child1, child2)) => {
+                    stack.push(child1);
+                    stack.push(child2);
+                }
+                None => callback(node.slot),
+            }
+        }
+    }
+
+    fn proxy(&self, slot: usize) -> &Proxy {
+        self.proxies[slot].as_ref().unwrap()
+    }
+
+    fn find_proxy(&self, handle: BodyHandle) -> Option<&Proxy> {
+        self.proxies
+            .get(handle.slot())
+            .and_then(|proxy| proxy.as_ref())
+            .filter(|proxy| proxy.handle == handle)
+    }
+
+    fn fat_aabb(&self, handle: BodyHandle) -> &Aabb {
+        &self.nodes[self.proxy(handle.slot()).leaf].aabb
+    }
+}
+
+impl Default for DynamicTree {
+    fn default() -> Self {
+        Self::with_margin(SolverConfig::default().aabb_margin)
+    }
+}
+
+impl BroadPhase for DynamicTree {
+    fn insert(&mut self, handle: BodyHandle, aabb: Aabb) {
+        let slot = handle.slot();
+        if self.proxies.len() <= slot {
+            self.proxies.resize_with(slot + 1, || None);
+        }
+
+        let leaf = self.allocate_node(Node {
+            aabb: aabb.fattened(self.margin),
+            parent: None,
+            children: None,
+            height: 0,
+            slot,
+        });
+        self.insert_leaf(leaf);
+        self.proxies[slot] = Some(Proxy { handle, aabb, leaf });
+        self.moved.push(handle);
+    }
+
+    fn remove(&mut self, handle: BodyHandle) {
+        let leaf = match self.find_proxy(handle) {
+            Some(proxy) => proxy.leaf,
+            None => return,
+        };
+        self.remove_leaf(leaf);
+        self.free_nodes.push(leaf);
+        self.moved.retain(|&moved| moved != handle);
+        self.candidates
+            .retain(|&(handle1, handle2)| handle1 != handle && handle2 != handle);
+        self.pairs.remove(handle);
+        self.proxies[handle.slot()] = None;
+    }
+
+    fn set_aabb(&mut self, handle: BodyHandle, aabb: Aabb) {
+        let leaf = match self.find_proxy(handle) {
+            Some(proxy) => proxy.leaf,
+            None => return,
+        };
+        self.proxies[handle.slot()].as_mut().unwrap().aabb = aabb;
+
+        if !self.nodes[leaf].aabb.contains(&aabb) {
+            self.remove_leaf(leaf);
+            self.nodes[leaf].aabb = aabb.fattened(self.margin);
+            self.insert_leaf(leaf);
+            self.moved.push(handle);
+        }
+    }
+
+    // 放大后的包围盒只在重新插入时改变：先丢弃其中移动过的物体不再相交的候选，
+    // 再以移动过的物体放大后的包围盒查询树补充候选；最后用实际的包围盒判断是否相交。
+    fn update_pairs(&mut self) {
+        let moved: HashSet<BodyHandle> = self.moved.drain(..).collect();
+        let mut candidates: HashSet<(BodyHandle, BodyHandle)> =
+            std::mem::take(&mut self.candidates)
+                .into_iter()
+                .filter(|&(handle1, handle2)| {
+                    !moved.contains(&handle1) && !moved.contains(&handle2)
+                        || self.fat_aabb(handle1).overlaps(self.fat_aabb(handle2))
+                })
+                .collect();
+        for &handle in &moved {
+            self.for_each_leaf(self.fat_aabb(handle), |other| {
+                let other = self.proxy(other).handle;
+                if other != handle {
+                    candidates.insert((handle.min(other), handle.max(other)));
+                }
+            });
+        }
+
+        let pairs: HashSet<(BodyHandle, BodyHandle)> = candidates
+            .iter()
+            .copied()
+            .filter(|&(handle1, handle2)| {
+                let aabb1 = &self.proxy(handle1.slot()).aabb;
+                aabb1.overlaps(&self.proxy(handle2.slot()).aabb)
+            })
+            .collect();
+        self.candidates = candidates;
+        self.pairs.update(pairs);
+    }
+
+    fn pairs(&self) -> Vec<(BodyHandle, BodyHandle)> {
+        self.pairs.pairs()
+    }
+
+    fn began(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.pairs.began
+    }
+
+    fn ended(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.pairs.ended
+    }
+
+    fn query(&self, region: &Aabb) -> Vec<BodyHandle> {
+        let mut handles: Vec<BodyHandle> = Vec::new();
+        self.for_each_leaf(region, |slot| {
+            let proxy = self.proxy(slot);
+            if proxy.aabb.overlaps(region) {
+                handles.push(proxy.handle);
+            }
+        });
+        handles.sort_unstable();
+        handles
+    }
+}
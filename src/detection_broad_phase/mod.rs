@@ -0,0 +1,230 @@
+use crate::{algebra::Float, body_set::BodyHandle, shapes::Bounded};
+use std::collections::HashSet;
+
+mod sweep_and_prune;
+pub use self::sweep_and_prune::SweepAndPrune;
+
+mod dynamic_tree;
+pub use self::dynamic_tree::DynamicTree;
+
+mod spatial_hash;
+pub use self::spatial_hash::{detect_by_spatial_hash, SpatialHash, SpatialHashError};
+
+// 增量式的宽检测：物体增删时同步插入与移除，每帧更新包围盒后调用 update_pairs，
+// 得到包围盒相交的物体对及其相对上一次更新的变化。物体对中句柄较小者在前。
+pub trait BroadPhase {
+    fn insert(&mut self, handle: BodyHandle, aabb: Aabb);
+    fn remove(&mut self, handle: BodyHandle);
+    fn set_aabb(&mut self, handle: BodyHandle, aabb: Aabb);
+    fn update_pairs(&mut self);
+    // 按句柄排序。
+    fn pairs(&self) -> Vec<(BodyHandle, BodyHandle)>;
+    fn began(&self) -> &[(BodyHandle, BodyHandle)];
+    fn ended(&self) -> &[(BodyHandle, BodyHandle)];
+    // 包围盒与给定区域相交的物体，按句柄排序。
+    fn query(&self, region: &Aabb) -> Vec<BodyHandle>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeIndexPair(pub usize, pub usize);
+
+impl PartialEq for ShapeIndexPair {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.0 == rhs.0 && self.1 == rhs.1 || self.0 == rhs.1 && self.1 == rhs.0
+    }
+}
+
+impl Eq for ShapeIndexPair {}
+
+struct ShapeBoundPoint {
+    index: usize,
+    bound: Float,
+    is_start: bool,
+}
+
+pub fn detect_by_broad_phase(shapes: &Vec<&impl Bounded>) -> Vec<ShapeIndexPair> {
+    let x_axis_results = detect_axis_by_broad_phase(
+        shapes,
+        |shape| shape.bound_left(),
+        |shape| shape.bound_right(),
+    );
+    let y_axis_results = detect_axis_by_broad_phase(
+        shapes,
+        |shape| shape.bound_bottom(),
+        |shape| shape.bound_top(),
+    );
+
+    let x_axis_results: HashSet<(usize, usize)> = x_axis_results
+        .into_iter()
+        .map(|ShapeIndexPair(index1, index2)| (index1.min(index2), index1.max(index2)))
+        .collect();
+    y_axis_results
+        .into_iter()
+        .filter(|&ShapeIndexPair(index1, index2)| {
+            x_axis_results.contains(&(index1.min(index2), index1.max(index2)))
+        })
+        .collect()
+}
+
+fn detect_axis_by_broad_phase(
+    shapes: &[&impl Bounded],
+    get_min: impl Fn(&dyn Bounded) -> Float,
+    get_max: impl Fn(&dyn Bounded) -> Float,
+) -> Vec<ShapeIndexPair> {
+    let mut points: Vec<ShapeBoundPoint> = Vec::new();
+    for (i, &shape) in shapes.iter().enumerate() {
+        points.push(ShapeBoundPoint {
+            index: i,
+            bound: get_min(shape),
+            is_start: true,
+        });
+        points.push(ShapeBoundPoint {
+            index: i,
+            bound: get_max(shape),
+            is_start: false,
+        });
+    }
+
+    detect_by_broad_phase_core(&mut points)
+}
+
+fn detect_by_broad_phase_core(shapes: &mut Vec<ShapeBoundPoint>) -> Vec<ShapeIndexPair> {
+    shapes.sort_by(|x, y| x.bound.partial_cmp(&y.bound).unwrap());
+
+    let mut activated_ids: HashSet<usize> = HashSet::new();
+    let mut result: Vec<ShapeIndexPair> = Vec::new();
+    for ShapeBoundPoint {
+        index,
+        bound: _,
+        is_start,
+    } in shapes
+    {
+        if *is_start {
+            for activated_id in &activated_ids {
+                result.push(ShapeIndexPair(*index, *activated_id));
+            }
+
+            activated_ids.insert(*index);
+        } else {
+            activated_ids.remove(index);
+        }
+    }
+
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub left: Float,
+    pub bottom: Float,
+    pub right: Float,
+    pub top: Float,
+}
+
+impl Aabb {
+    pub fn of(shape: &(impl Bounded + ?Sized)) -> Self {
+        Self {
+            left: shape.bound_left(),
+            bottom: shape.bound_bottom(),
+            right: shape.bound_right(),
+            top: shape.bound_top(),
+        }
+    }
+
+    // 相切不算相交。
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.left < other.right
+            && other.left < self.right
+            && self.bottom < other.top
+            && other.bottom < self.top
+    }
+
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.left <= other.left
+            && self.bottom <= other.bottom
+            && other.right <= self.right
+            && other.top <= self.top
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            left: self.left.min(other.left),
+            bottom: self.bottom.min(other.bottom),
+            right: self.right.max(other.right),
+            top: self.top.max(other.top),
+        }
+    }
+
+    pub fn fattened(&self, margin: Float) -> Aabb {
+        Aabb {
+            left: self.left - margin,
+            bottom: self.bottom - margin,
+            right: self.right + margin,
+            top: self.top + margin,
+        }
+    }
+
+    pub fn perimeter(&self) -> Float {
+        2.0 * ((self.right - self.left) + (self.top - self.bottom))
+    }
+
+    fn bounds(&self, axis: usize) -> (Float, Float) {
+        if axis == 0 {
+            (self.left, self.right)
+        } else {
+            (self.bottom, self.top)
+        }
+    }
+}
+
+// 每次整体重新计算物体对的宽检测，通过与上一次的结果比较得出变化。
+struct PairTracker {
+    pairs: HashSet<(BodyHandle, BodyHandle)>,
+    began: Vec<(BodyHandle, BodyHandle)>,
+    ended: Vec<(BodyHandle, BodyHandle)>,
+    // 两次更新之间因删除物体而结束相交的物体对，并入下一次更新的 ended。
+    removed: Vec<(BodyHandle, BodyHandle)>,
+}
+
+impl PairTracker {
+    fn new() -> Self {
+        Self {
+            pairs: HashSet::new(),
+            began: Vec::new(),
+            ended: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    fn remove(&mut self, handle: BodyHandle) {
+        let mut removed: Vec<(BodyHandle, BodyHandle)> = self
+            .pairs
+            .iter()
+            .copied()
+            .filter(|&(handle1, handle2)| handle1 == handle || handle2 == handle)
+            .collect();
+        removed.sort_unstable();
+        for pair in &removed {
+            self.pairs.remove(pair);
+        }
+        self.removed.extend(removed);
+    }
+
+    // pairs 中句柄较小者在前。
+    fn update(&mut self, pairs: HashSet<(BodyHandle, BodyHandle)>) {
+        self.began = pairs.difference(&self.pairs).copied().collect();
+        self.began.sort_unstable();
+        let mut ended: Vec<(BodyHandle, BodyHandle)> =
+            self.pairs.difference(&pairs).copied().collect();
+        ended.sort_unstable();
+        self.ended = std::mem::take(&mut self.removed);
+        self.ended.extend(ended);
+        self.pairs = pairs;
+    }
+
+    fn pairs(&self) -> Vec<(BodyHandle, BodyHandle)> {
+        let mut pairs: Vec<(BodyHandle, BodyHandle)> = self.pairs.iter().copied().collect();
+        pairs.sort_unstable();
+        pairs
+    }
+}
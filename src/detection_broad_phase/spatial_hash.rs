@@ -0,0 +1,241 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+use super::{Aabb, BroadPhase, PairTracker, ShapeIndexPair};
+use crate::{algebra::Float, body_set::BodyHandle, shapes::Bounded};
+
+// 单个物体在每个轴上最多覆盖的格子数，超出的物体（如地面）不登记到网格中，而是与所有物体逐一比较。
+const MAX_CELL_SPAN: i64 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialHashError {
+    InvalidCellSize,
+}
+
+impl fmt::Display for SpatialHashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpatialHashError::InvalidCellSize => {
+                write!(f, "the cell size must be positive and finite")
+            }
+        }
+    }
+}
+
+impl Error for SpatialHashError {}
+
+// 均匀网格：每个物体登记到其包围盒覆盖的所有格子中，只在同一格子内两两比较，
+// 适合大量尺寸相近的物体。cell_size 为 None 时取各物体包围盒较长边的中位数。
+// 结果与 detect_by_broad_phase 完全相同，包括包围盒相切时的判定。
+pub fn detect_by_spatial_hash(
+    shapes: &[&impl Bounded],
+    cell_size: Option<Float>,
+) -> Result<Vec<ShapeIndexPair>, SpatialHashError> {
+    if let Some(cell_size) = cell_size {
+        validate_cell_size(cell_size)?;
+    }
+
+    let aabbs: Vec<Aabb> = shapes.iter().map(|&shape| Aabb::of(shape)).collect();
+    Ok(find_pairs(&aabbs, cell_size, |index1, index2| {
+        overlaps_in_order(&aabbs[index1], &aabbs[index2])
+    })
+    .into_iter()
+    .map(|(index1, index2)| ShapeIndexPair(index1, index2))
+    .collect())
+}
+
+struct Proxy {
+    handle: BodyHandle,
+    aabb: Aabb,
+}
+
+// 网格每次更新时重新建立，与上一次的物体对比较得出变化。
+pub struct SpatialHash {
+    cell_size: Option<Float>,
+    proxies: Vec<Option<Proxy>>,
+    pairs: PairTracker,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: Float) -> Result<Self, SpatialHashError> {
+        validate_cell_size(cell_size)?;
+        Ok(Self {
+            cell_size: Some(cell_size),
+            ..Self::default()
+        })
+    }
+}
+
+fn validate_cell_size(cell_size: Float) -> Result<(), SpatialHashError> {
+    if cell_size > 0.0 && cell_size.is_finite() {
+        Ok(())
+    } else {
+        Err(SpatialHashError::InvalidCellSize)
+    }
+}
+
+impl Default for SpatialHash {
+    fn default() -> Self {
+        Self {
+            cell_size: None,
+            proxies: Vec::new(),
+            pairs: PairTracker::new(),
+        }
+    }
+}
+
+impl BroadPhase for SpatialHash {
+    fn insert(&mut self, handle: BodyHandle, aabb: Aabb) {
+        let slot = handle.slot();
+        if self.proxies.len() <= slot {
+            self.proxies.resize_with(slot + 1, || None);
+        }
+        self.proxies[slot] = Some(Proxy { handle, aabb });
+    }
+
+    fn remove(&mut self, handle: BodyHandle) {
+        if let Some(proxy) = self.proxies.get_mut(handle.slot()) {
+            if proxy.as_ref().is_some_and(|proxy| proxy.handle == handle) {
+                *proxy = None;
+                self.pairs.remove(handle);
+            }
+        }
+    }
+
+    fn set_aabb(&mut self, handle: BodyHandle, aabb: Aabb) {
+        if let Some(Some(proxy)) = self.proxies.get_mut(handle.slot()) {
+            if proxy.handle == handle {
+                proxy.aabb = aabb;
+            }
+        }
+    }
+
+    fn update_pairs(&mut self) {
+        let proxies: Vec<&Proxy> = self.proxies.iter().flatten().collect();
+        let aabbs: Vec<Aabb> = proxies.iter().map(|proxy| proxy.aabb).collect();
+        let pairs: HashSet<(BodyHandle, BodyHandle)> =
+            find_pairs(&aabbs, self.cell_size, |index1, index2| {
+                aabbs[index1].overlaps(&aabbs[index2])
+            })
+            .into_iter()
+            .map(|(index1, index2)| (proxies[index1].handle, proxies[index2].handle))
+            .collect();
+        self.pairs.update(pairs);
+    }
+
+    fn pairs(&self) -> Vec<(BodyHandle, BodyHandle)> {
+        self.pairs.pairs()
+    }
+
+    fn began(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.pairs.began
+    }
+
+    fn ended(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.pairs.ended
+    }
+
+    fn query(&self, region: &Aabb) -> Vec<BodyHandle> {
+        let mut handles: Vec<BodyHandle> = self
+            .proxies
+            .iter()
+            .flatten()
+            .filter(|proxy| proxy.aabb.overlaps(region))
+            .map(|proxy| proxy.handle)
+            .collect();
+        handles.sort_unstable();
+        handles
+    }
+}
+
+// 返回的下标对中较小者在前，并按下标排序；overlaps 的两个参数同样较小者在前。
+fn find_pairs(
+    aabbs: &[Aabb],
+    cell_size: Option<Float>,
+    overlaps: impl Fn(usize, usize) -> bool,
+) -> Vec<(usize, usize)> {
+    // 所有包围盒都退化为点时，不存在相交的物体对。
+    let cell_size = match cell_size.or_else(|| median_extent(aabbs)) {
+        Some(cell_size) => cell_size,
+        None => return Vec::new(),
+    };
+    let cell = |value: Float| (value / cell_size).floor() as i64;
+
+    let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    let mut oversized: Vec<usize> = Vec::new();
+    for (index, aabb) in aabbs.iter().enumerate() {
+        let (left, right) = (cell(aabb.left), cell(aabb.right));
+        let (bottom, top) = (cell(aabb.bottom), cell(aabb.top));
+        if right.saturating_sub(left) >= MAX_CELL_SPAN
+            || top.saturating_sub(bottom) >= MAX_CELL_SPAN
+        {
+            oversized.push(index);
+            continue;
+        }
+        for x in left..=right {
+            for y in bottom..=top {
+                cells.entry((x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    for &index1 in &oversized {
+        for index2 in 0..aabbs.len() {
+            // 两者都过大时只由下标较小者记录一次。
+            let counted = index2 < index1 && oversized.binary_search(&index2).is_ok();
+            if index2 != index1 && !counted {
+                let (index1, index2) = (index1.min(index2), index1.max(index2));
+                if overlaps(index1, index2) {
+                    pairs.push((index1, index2));
+                }
+            }
+        }
+    }
+    for (&(x, y), indices) in &cells {
+        for (i, &index1) in indices.iter().enumerate() {
+            for &index2 in &indices[i + 1..] {
+                let (aabb1, aabb2) = (&aabbs[index1], &aabbs[index2]);
+                // 同一对物体可能同时出现在多个格子中，只在两者交集左下角所在的格子里记录一次。
+                if overlaps(index1, index2)
+                    && cell(aabb1.left.max(aabb2.left)) == x
+                    && cell(aabb1.bottom.max(aabb2.bottom)) == y
+                {
+                    pairs.push((index1, index2));
+                }
+            }
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+// detect_by_broad_phase 按数值稳定排序，数值相同的端点保持输入顺序：
+// 前一个物体的结束点排在后一个物体的开始点之前，前一个物体的开始点排在后一个物体的结束点之前。
+// 因此相切时，仅当前一个物体开始于后一个物体的结束处才算相交。
+fn overlaps_in_order(aabb1: &Aabb, aabb2: &Aabb) -> bool {
+    aabb1.left <= aabb2.right
+        && aabb2.left < aabb1.right
+        && aabb1.bottom <= aabb2.top
+        && aabb2.bottom < aabb1.top
+}
+
+// 包围盒较长边的中位数；中位数为零时退而取最大值，全部为零时返回 None。
+fn median_extent(aabbs: &[Aabb]) -> Option<Float> {
+    let mut extents: Vec<Float> = aabbs
+        .iter()
+        .map(|aabb| (aabb.right - aabb.left).max(aabb.top - aabb.bottom))
+        .collect();
+    extents.sort_by(|x, y| x.partial_cmp(y).unwrap());
+
+    let median = *extents.get(extents.len() / 2)?;
+    let largest = *extents.last()?;
+    [median, largest]
+        .iter()
+        .copied()
+        .find(|&extent| extent > 0.0)
+}
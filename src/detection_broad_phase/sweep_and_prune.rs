@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use super::{Aabb, BroadPhase};
+use crate::{algebra::Float, body_set::BodyHandle};
+
+#[derive(Debug, Clone, Copy)]
+struct Endpoint {
+    slot: usize,
+    value: Float,
+    is_min: bool,
+}
+
+impl Endpoint {
+    // 数值相同时上界排在下界之前，使相切的两者不被视为相交。
+    fn precedes(&self, other: &Endpoint) -> bool {
+        self.value < other.value || self.value == other.value && !self.is_min && other.is_min
+    }
+}
+
+struct Proxy {
+    handle: BodyHandle,
+    aabb: Aabb,
+}
+
+// 增量式排序扫描：两个轴上的端点数组跨帧保留，物体每帧移动很小，插入排序只需少量交换。
+// 端点交换时即可得知相交状态的变化：下界越过上界时可能开始相交，上界越过下界时不再相交。
+// 由此维护相交的物体对集合，并给出本次更新中开始与结束相交的物体对。
+pub struct SweepAndPrune {
+    proxies: Vec<Option<Proxy>>,
+    axes: [Vec<Endpoint>; 2],
+    pairs: HashSet<(usize, usize)>,
+    began: Vec<(BodyHandle, BodyHandle)>,
+    ended: Vec<(BodyHandle, BodyHandle)>,
+    // 两次更新之间因删除物体而结束相交的物体对，并入下一次更新的 ended。
+    removed: Vec<(BodyHandle, BodyHandle)>,
+}
+
+impl SweepAndPrune {
+    pub fn new() -> Self {
+        Self {
+            proxies: Vec::new(),
+            axes: [Vec::new(), Vec::new()],
+            pairs: HashSet::new(),
+            began: Vec::new(),
+            ended: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    fn begin_pair(&mut self, slot1: usize, slot2: usize) {
+        if slot1 == slot2 || !self.aabb(slot1).overlaps(&self.aabb(slot2)) {
+            return;
+        }
+        let pair = (slot1.min(slot2), slot1.max(slot2));
+        if self.pairs.insert(pair) {
+            self.began.push(self.handles(pair));
+        }
+    }
+
+    fn end_pair(&mut self, slot1: usize, slot2: usize) {
+        let pair = (slot1.min(slot2), slot1.max(slot2));
+        if self.pairs.remove(&pair) {
+            self.ended.push(self.handles(pair));
+        }
+    }
+
+    fn aabb(&self, slot: usize) -> Aabb {
+        self.proxy(slot).aabb
+    }
+
+    fn handles(&self, (slot1, slot2): (usize, usize)) -> (BodyHandle, BodyHandle) {
+        (self.proxy(slot1).handle, self.proxy(slot2).handle)
+    }
+
+    fn proxy(&self, slot: usize) -> &Proxy {
+        self.proxies[slot].as_ref().unwrap()
+    }
+}
+
+impl Default for SweepAndPrune {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BroadPhase for SweepAndPrune {
+    // 新端点追加在末尾，与其他物体的相交关系在下一次 update_pairs 中经由插入排序得出。
+    fn insert(&mut self, handle: BodyHandle, aabb: Aabb) {
+        let slot = handle.slot();
+        if self.proxies.len() <= slot {
+            self.proxies.resize_with(slot + 1, || None);
+        }
+        self.proxies[slot] = Some(Proxy { handle, aabb });
+
+        for (axis, endpoints) in self.axes.iter_mut().enumerate() {
+            let (min, max) = aabb.bounds(axis);
+            endpoints.push(Endpoint {
+                slot,
+                value: min,
+                is_min: true,
+            });
+            endpoints.push(Endpoint {
+                slot,
+                value: max,
+                is_min: false,
+            });
+        }
+    }
+
+    fn remove(&mut self, handle: BodyHandle) {
+        let slot = handle.slot();
+        match self.proxies.get(slot) {
+            Some(Some(proxy)) if proxy.handle == handle => {}
+            _ => return,
+        }
+
+        for endpoints in &mut self.axes {
+            endpoints.retain(|endpoint| endpoint.slot != slot);
+        }
+
+        let mut removed: Vec<(usize, usize)> = self
+            .pairs
+            .iter()
+            .copied()
+            .filter(|&(slot1, slot2)| slot1 == slot || slot2 == slot)
+            .collect();
+        removed.sort_unstable();
+        for pair in removed {
+            self.pairs.remove(&pair);
+            self.removed.push(self.handles(pair));
+        }
+
+        self.proxies[slot] = None;
+    }
+
+    fn set_aabb(&mut self, handle: BodyHandle, aabb: Aabb) {
+        if let Some(Some(proxy)) = self.proxies.get_mut(handle.slot()) {
+            if proxy.handle == handle {
+                proxy.aabb = aabb;
+            }
+        }
+    }
+
+    // 按最新的包围盒重新排序端点，began 与 ended 记录自上次调用以来的变化。
+    fn update_pairs(&mut self) {
+        self.began.clear();
+        self.ended = std::mem::take(&mut self.removed);
+
+        for axis in 0..self.axes.len() {
+            let mut endpoints = std::mem::take(&mut self.axes[axis]);
+            for endpoint in &mut endpoints {
+                let (min, max) = self.aabb(endpoint.slot).bounds(axis);
+                endpoint.value = if endpoint.is_min { min } else { max };
+            }
+
+            for i in 1..endpoints.len() {
+                let moving = endpoints[i];
+                let mut j = i;
+                while j > 0 && moving.precedes(&endpoints[j - 1]) {
+                    let passed = endpoints[j - 1];
+                    if moving.is_min && !passed.is_min {
+                        self.begin_pair(moving.slot, passed.slot);
+                    } else if !moving.is_min && passed.is_min {
+                        self.end_pair(moving.slot, passed.slot);
+                    }
+                    endpoints[j] = passed;
+                    j -= 1;
+                }
+                endpoints[j] = moving;
+            }
+
+            self.axes[axis] = endpoints;
+        }
+    }
+
+    fn pairs(&self) -> Vec<(BodyHandle, BodyHandle)> {
+        let mut pairs: Vec<(BodyHandle, BodyHandle)> =
+            self.pairs.iter().map(|&pair| self.handles(pair)).collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    fn began(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.began
+    }
+
+    fn ended(&self) -> &[(BodyHandle, BodyHandle)] {
+        &self.ended
+    }
+
+    fn query(&self, region: &Aabb) -> Vec<BodyHandle> {
+        let mut handles: Vec<BodyHandle> = self
+            .proxies
+            .iter()
+            .flatten()
+            .filter(|proxy| proxy.aabb.overlaps(region))
+            .map(|proxy| proxy.handle)
+            .collect();
+        handles.sort_unstable();
+        handles
+    }
+}
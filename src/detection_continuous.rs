@@ -0,0 +1,154 @@
+use crate::{
+    algebra::Float,
+    contact_solver::SolverConfig,
+    detection_narrow_phase::CollisionInfo,
+    shapes::{Body, Bounded, Collider, RigidBodyLike, Transform, Wall},
+};
+
+const TIME_OF_IMPACT_ITERATIONS: usize = 20;
+
+// 连续碰撞检测：对于标记为 bullet 的物体，沿其在本帧起止位姿间的轨迹逐段采样，
+// 找到最早发生碰撞的时刻并二分细化，再将其退回到该时刻的位姿，交由接触求解器处理碰撞。
+// 退回后本帧剩余的运动时间被舍弃。
+pub fn solve_time_of_impact<T>(
+    bodies: &mut [Body<T>],
+    walls: &[Wall],
+    starts: &[Transform],
+    config: &SolverConfig,
+) where
+    T: Collider + Bounded + RigidBodyLike,
+{
+    let mut ends: Vec<Transform> = bodies
+        .iter()
+        .map(|body| body.rigid_body().transform())
+        .collect();
+
+    for index in 0..bodies.len() {
+        if !bodies[index].rigid_body().bullet || bodies[index].fixtures().is_empty() {
+            continue;
+        }
+
+        let mut earliest: Float = 1.0;
+        for wall in walls {
+            let displacement = ends[index].position - starts[index].position;
+            let steps = count_steps(&bodies[index], displacement.length());
+            let time = find_time_of_impact(steps, |t| {
+                let body = &mut bodies[index];
+                set_transform(body, starts[index].lerp(ends[index], t));
+                body.fixtures().iter().any(|fixture| {
+                    fixture.filter.should_collide(&wall.filter)
+                        && is_colliding(fixture.shape.detect_with_wall(wall, config), config)
+                })
+            });
+            set_transform(&mut bodies[index], ends[index]);
+            if let Some(time) = time {
+                earliest = earliest.min(time);
+            }
+        }
+
+        for other in (0..bodies.len()).filter(|&other| other != index) {
+            if !is_sweep_overlapping(bodies, (index, other), starts, &ends) {
+                continue;
+            }
+
+            let displacement = (ends[index].position - starts[index].position)
+                - (ends[other].position - starts[other].position);
+            let steps = count_steps(&bodies[index], displacement.length());
+            let time = find_time_of_impact(steps, |t| {
+                set_transform(&mut bodies[index], starts[index].lerp(ends[index], t));
+                set_transform(&mut bodies[other], starts[other].lerp(ends[other], t));
+                are_colliding(&bodies[index], &bodies[other], config)
+            });
+            set_transform(&mut bodies[index], ends[index]);
+            set_transform(&mut bodies[other], ends[other]);
+            if let Some(time) = time {
+                earliest = earliest.min(time);
+            }
+        }
+
+        if earliest < 1.0 {
+            ends[index] = starts[index].lerp(ends[index], earliest);
+            set_transform(&mut bodies[index], ends[index]);
+        }
+    }
+}
+
+// 在 (0, 1] 内寻找开始碰撞的最早时刻；起始时刻已经接触时不做处理，交由接触求解器。
+pub fn find_time_of_impact(steps: usize, mut collides: impl FnMut(Float) -> bool) -> Option<Float> {
+    if collides(0.0) {
+        return None;
+    }
+
+    let mut previous = 0.0;
+    for step in 1..=steps {
+        let t = step as Float / steps as Float;
+        if collides(t) {
+            let (mut low, mut high) = (previous, t);
+            for _ in 0..TIME_OF_IMPACT_ITERATIONS {
+                let middle = (low + high) / 2.0;
+                if collides(middle) {
+                    high = middle;
+                } else {
+                    low = middle;
+                }
+            }
+            return Some(high);
+        }
+        previous = t;
+    }
+
+    None
+}
+
+// 每段采样的相对位移不超过物体包围盒较窄一边的一半，使物体不会在两次采样之间穿过其他物体；
+// 旋转带来的位移不计入其中。
+fn count_steps(shape: &impl Bounded, distance: Float) -> usize {
+    let extent =
+        (shape.bound_right() - shape.bound_left()).min(shape.bound_top() - shape.bound_bottom());
+    ((distance / (extent / 2.0)).ceil() as usize).max(1)
+}
+
+fn is_colliding(info: Option<CollisionInfo>, config: &SolverConfig) -> bool {
+    info.is_some_and(|info| info.penetration > config.linear_slop)
+}
+
+// 两者的夹具中是否有未被过滤的一对发生碰撞。
+fn are_colliding<T: Collider + RigidBodyLike>(
+    body1: &Body<T>,
+    body2: &Body<T>,
+    config: &SolverConfig,
+) -> bool {
+    body1.fixtures().iter().any(|fixture1| {
+        body2.fixtures().iter().any(|fixture2| {
+            fixture1.filter.should_collide(&fixture2.filter)
+                && is_colliding(fixture1.shape.detect_with(&fixture2.shape, config), config)
+        })
+    })
+}
+
+fn set_transform<T: RigidBodyLike>(body: &mut Body<T>, transform: Transform) {
+    body.rigid_body_mut().set_transform(transform);
+    body.synchronize_fixtures();
+}
+
+// 两者在本帧扫过的轴对齐包围盒是否相交，旋转带来的变化不计入其中。
+fn is_sweep_overlapping<T: Bounded>(
+    bodies: &[T],
+    (index1, index2): (usize, usize),
+    starts: &[Transform],
+    ends: &[Transform],
+) -> bool {
+    let bounds = |index: usize| {
+        let body = &bodies[index];
+        let offset = starts[index].position - ends[index].position;
+        (
+            body.bound_left() + offset.x.min(0.0),
+            body.bound_bottom() + offset.y.min(0.0),
+            body.bound_right() + offset.x.max(0.0),
+            body.bound_top() + offset.y.max(0.0),
+        )
+    };
+    let (left1, bottom1, right1, top1) = bounds(index1);
+    let (left2, bottom2, right2, top2) = bounds(index2);
+    left1 < right2 && left2 < right1 && bottom1 < top2 && bottom2 < top1
+}
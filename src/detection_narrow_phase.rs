@@ -1,27 +1,956 @@
 use crate::{
-    algebra::{Float, Vec2},
-    shapes::{Circle, RigidBodyLike},
+    algebra::{Float, Mat22, Vec2},
+    contact_solver::SolverConfig,
+    shapes::{Capsule, Circle, Polygon, Rectangle, RigidBodyLike, Segment, Wall},
 };
 
+const CLIPPED_BY_SIDE1: u32 = 0x8000;
+const CLIPPED_BY_SIDE2: u32 = 0x8001;
+const CLOSEST_POINTS: u32 = 0x8002;
+const CLOSEST_VERTEX: u32 = 0x8003;
+
+// 接触点的特征编号，由产生该接触点的顶点与边决定，用于跨帧匹配同一个接触点。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContactFeature(pub u32);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Contact {
+    pub point: Vec2,
+    pub feature: ContactFeature,
+}
+
 pub struct CollisionInfo {
     pub penetration: Float,
     pub normal: Vec2,
+    pub contacts: Vec<Contact>,
+}
+
+impl CollisionInfo {
+    // 交换两个物体的先后次序，法线随之反向。
+    pub fn flip(mut self) -> Self {
+        self.normal = -self.normal;
+        self
+    }
+}
+
+impl Contact {
+    fn new(point: Vec2, feature: u32) -> Self {
+        Self {
+            point,
+            feature: ContactFeature(feature),
+        }
+    }
 }
 
 pub fn detect_collision_circle_and_circle(
     circle1: &Circle,
     circle2: &Circle,
+    config: &SolverConfig,
 ) -> Option<CollisionInfo> {
     let min_distance = circle1.radius + circle2.radius;
     let normal = circle2.rigid_body().position - circle1.rigid_body().position;
     let actual_distance_squared = normal.length_squared();
     if actual_distance_squared < min_distance * min_distance {
         let actual_distance = actual_distance_squared.sqrt();
+        let penetration = min_distance - actual_distance;
+        // 两圆心重合时方向无从确定，取竖直向上将两者分开。
+        let normal = if actual_distance > config.float_tolerance {
+            normal / actual_distance
+        } else {
+            Vec2::new(0.0, 1.0)
+        };
         return Some(CollisionInfo {
-            penetration: min_distance - actual_distance,
-            normal: normal / actual_distance,
+            penetration,
+            normal,
+            contacts: vec![Contact::new(
+                circle1.rigid_body().position + normal * (circle1.radius - penetration / 2.0),
+                0,
+            )],
         });
     }
 
-    return None;
+    None
+}
+
+pub fn detect_collision_circle_and_rectangle(
+    circle: &Circle,
+    rect: &Rectangle,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let rotation = Mat22::rotation(rect.rigid_body().angle);
+    let inverse_rotation = Mat22::rotation(-rect.rigid_body().angle);
+    let half_width = rect.width / 2.0;
+    let half_height = rect.height / 2.0;
+
+    // 在矩形的局部坐标系下计算：矩形中心为原点，边与坐标轴平行。
+    let center = inverse_rotation * (circle.rigid_body().position - rect.rigid_body().position);
+    let closest = Vec2::new(
+        center.x.max(-half_width).min(half_width),
+        center.y.max(-half_height).min(half_height),
+    );
+
+    if same_point(closest, center, config) {
+        // 圆心在矩形内部：沿最近的边将两者分开。
+        let distance_x = half_width - center.x.abs();
+        let distance_y = half_height - center.y.abs();
+        let (local_normal, distance) = if distance_x < distance_y {
+            (Vec2::new(-center.x.signum(), 0.0), distance_x)
+        } else {
+            (Vec2::new(0.0, -center.y.signum()), distance_y)
+        };
+
+        return Some(circle_contact(
+            circle,
+            circle.radius + distance,
+            rotation * local_normal,
+        ));
+    }
+
+    let offset = closest - center;
+    let distance_squared = offset.length_squared();
+    if distance_squared < circle.radius * circle.radius {
+        let distance = distance_squared.sqrt();
+        return Some(circle_contact(
+            circle,
+            circle.radius - distance,
+            rotation * (offset / distance),
+        ));
+    }
+
+    None
+}
+
+pub fn detect_collision_rectangle_and_rectangle(
+    rect1: &Rectangle,
+    rect2: &Rectangle,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    detect_collision_convex(&rect1.vertices(), &rect2.vertices(), config)
+}
+
+pub fn detect_collision_circle_and_polygon(
+    circle: &Circle,
+    polygon: &Polygon,
+    _config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let center = circle.rigid_body().position;
+    let radius = circle.radius;
+    let vertices = polygon.vertices();
+    let count = vertices.len();
+
+    // 找出圆心分离距离最大的边。
+    let mut edge = 0;
+    let mut max_separation = Float::NEG_INFINITY;
+    for i in 0..count {
+        let separation =
+            edge_normal(vertices[i], vertices[(i + 1) % count]) * (center - vertices[i]);
+        if separation >= radius {
+            return None;
+        }
+        if separation > max_separation {
+            max_separation = separation;
+            edge = i;
+        }
+    }
+
+    let v1 = vertices[edge];
+    let v2 = vertices[(edge + 1) % count];
+    let face_normal = edge_normal(v1, v2);
+
+    // 圆心在多边形内部，沿该边推开。
+    if max_separation < 0.0 {
+        return Some(circle_contact(
+            circle,
+            radius - max_separation,
+            -face_normal,
+        ));
+    }
+
+    // 圆心位于边的端点区域时，最近点为该端点。
+    let closest = if (center - v1) * (v2 - v1) <= 0.0 {
+        v1
+    } else if (center - v2) * (v1 - v2) <= 0.0 {
+        v2
+    } else {
+        return Some(circle_contact(
+            circle,
+            radius - max_separation,
+            -face_normal,
+        ));
+    };
+
+    let offset = closest - center;
+    let distance_squared = offset.length_squared();
+    if distance_squared >= radius * radius {
+        return None;
+    }
+
+    let distance = distance_squared.sqrt();
+    Some(circle_contact(circle, radius - distance, offset / distance))
+}
+
+pub fn detect_collision_rectangle_and_polygon(
+    rect: &Rectangle,
+    polygon: &Polygon,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    detect_collision_convex(&rect.vertices(), &polygon.vertices(), config)
+}
+
+pub fn detect_collision_polygon_and_polygon(
+    polygon1: &Polygon,
+    polygon2: &Polygon,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    detect_collision_convex(&polygon1.vertices(), &polygon2.vertices(), config)
+}
+
+pub fn detect_collision_circle_and_capsule(
+    circle: &Circle,
+    capsule: &Capsule,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let center = circle.rigid_body().position;
+    let (start, end) = capsule.segment();
+    let offset = closest_point_on_segment(center, start, end) - center;
+    let min_distance = circle.radius + capsule.radius;
+    let distance_squared = offset.length_squared();
+    if distance_squared >= min_distance * min_distance {
+        return None;
+    }
+
+    let distance = distance_squared.sqrt();
+    // 圆心恰好位于线段上时，沿线段的法线方向分开；
+    // 长度为 0 的胶囊没有法线方向，与两圆心重合时相同，取竖直向上。
+    let normal = if distance > config.float_tolerance {
+        offset / distance
+    } else if !same_point(start, end, config) {
+        edge_normal(start, end)
+    } else {
+        Vec2::new(0.0, 1.0)
+    };
+    Some(circle_contact(circle, min_distance - distance, normal))
+}
+
+pub fn detect_collision_capsule_and_capsule(
+    capsule1: &Capsule,
+    capsule2: &Capsule,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let (start1, end1) = capsule1.segment();
+    let (start2, end2) = capsule2.segment();
+    detect_collision_rounded(
+        &[start1, end1],
+        capsule1.radius,
+        &[start2, end2],
+        capsule2.radius,
+        config,
+    )
+}
+
+pub fn detect_collision_capsule_and_rectangle(
+    capsule: &Capsule,
+    rect: &Rectangle,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let (start, end) = capsule.segment();
+    detect_collision_rounded(&[start, end], capsule.radius, &rect.vertices(), 0.0, config)
+}
+
+pub fn detect_collision_capsule_and_polygon(
+    capsule: &Capsule,
+    polygon: &Polygon,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let (start, end) = capsule.segment();
+    detect_collision_rounded(
+        &[start, end],
+        capsule.radius,
+        &polygon.vertices(),
+        0.0,
+        config,
+    )
+}
+
+pub fn detect_collision_segment_and_circle(
+    segment: &Segment,
+    circle: &Circle,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let center = circle.rigid_body().position;
+    let edge = Edge::facing(segment, center)?;
+    let closest = closest_point_on_segment(center, edge.start, edge.end);
+    // 连接处的顶点由下一条边负责，避免同一接触点出现两次。
+    if edge.next.is_some() && same_point(closest, edge.end, config) {
+        return None;
+    }
+
+    let offset = center - closest;
+    let distance_squared = offset.length_squared();
+    if distance_squared >= circle.radius * circle.radius {
+        return None;
+    }
+
+    let distance = distance_squared.sqrt();
+    let normal = if distance > config.float_tolerance {
+        offset / distance
+    } else {
+        edge.normal()
+    };
+    match edge.classify(normal, config.float_tolerance) {
+        NormalRegion::Own => Some(circle_contact(circle, circle.radius - distance, -normal).flip()),
+        NormalRegion::Neighbour | NormalRegion::Blocked => None,
+    }
+}
+
+pub fn detect_collision_segment_and_rectangle(
+    segment: &Segment,
+    rect: &Rectangle,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    detect_collision_segment_and_rounded(
+        segment,
+        &rect.vertices(),
+        0.0,
+        rect.rigid_body().position,
+        config,
+    )
+}
+
+pub fn detect_collision_segment_and_polygon(
+    segment: &Segment,
+    polygon: &Polygon,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    detect_collision_segment_and_rounded(
+        segment,
+        &polygon.vertices(),
+        0.0,
+        polygon.rigid_body().position,
+        config,
+    )
+}
+
+pub fn detect_collision_segment_and_capsule(
+    segment: &Segment,
+    capsule: &Capsule,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let (start, end) = capsule.segment();
+    detect_collision_segment_and_rounded(
+        segment,
+        &[start, end],
+        capsule.radius,
+        capsule.rigid_body().position,
+        config,
+    )
+}
+
+// 先把线段当作普通的凸形状检测，再按相邻的边修正法线：
+// 法线落在凹角内（例如方块越过两条共线的边的连接处，分离轴选中了方块的侧面）时，
+// 改为沿本边的法线接触，物体便不会被内部的棱角卡住。
+fn detect_collision_segment_and_rounded(
+    segment: &Segment,
+    vertices: &[Vec2],
+    radius: Float,
+    centroid: Vec2,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let edge = Edge::facing(segment, centroid)?;
+    let edge_vertices = [edge.start, edge.end];
+    let info = detect_collision_rounded(&edge_vertices, 0.0, vertices, radius, config)?;
+    match edge.classify(info.normal, config.float_tolerance) {
+        NormalRegion::Own => Some(info),
+        NormalRegion::Neighbour => None,
+        NormalRegion::Blocked => clip_rounded((&edge_vertices, 0.0), (vertices, radius), 0, false),
+    }
+}
+
+pub fn detect_collision_wall_and_circle(
+    wall: &Wall,
+    circle: &Circle,
+    _config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let normal = wall.normal();
+    let center = circle.rigid_body().position;
+    let penetration = wall.depth(center) + circle.radius;
+    if penetration <= 0.0 {
+        return None;
+    }
+
+    Some(CollisionInfo {
+        penetration,
+        normal,
+        contacts: vec![Contact::new(
+            center - normal * (circle.radius - penetration / 2.0),
+            0,
+        )],
+    })
+}
+
+pub fn detect_collision_wall_and_rectangle(
+    wall: &Wall,
+    rect: &Rectangle,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    detect_collision_wall_and_vertices(wall, &rect.vertices(), config)
+}
+
+pub fn detect_collision_wall_and_polygon(
+    wall: &Wall,
+    polygon: &Polygon,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    detect_collision_wall_and_vertices(wall, &polygon.vertices(), config)
+}
+
+pub fn detect_collision_wall_and_capsule(
+    wall: &Wall,
+    capsule: &Capsule,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let normal = wall.normal();
+    let (start, end) = capsule.segment();
+    let depth = |center: Vec2| wall.depth(center) + capsule.radius;
+    let penetration = depth(start).max(depth(end));
+    if penetration <= 0.0 {
+        return None;
+    }
+
+    // 与多边形的顶点相同，两端深度之差小于线性容差时都作为接触点。
+    let contacts = [start, end]
+        .iter()
+        .enumerate()
+        .filter(|(_, &center)| depth(center) > penetration - config.linear_slop)
+        .map(|(i, &center)| {
+            Contact::new(
+                center - normal * (capsule.radius - depth(center) / 2.0),
+                i as u32,
+            )
+        })
+        .collect();
+
+    Some(CollisionInfo {
+        penetration,
+        normal,
+        contacts,
+    })
+}
+
+fn detect_collision_wall_and_vertices(
+    wall: &Wall,
+    vertices: &[Vec2],
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let penetration = vertices
+        .iter()
+        .map(|&vertex| wall.depth(vertex))
+        .fold(Float::NEG_INFINITY, Float::max);
+    if penetration <= 0.0 {
+        return None;
+    }
+
+    // 最深的顶点可能不止一个，例如多边形的一条边与墙体平行时；
+    // 边与墙体的夹角小于线性容差时也将两端均视为接触点，避免物体绕单个顶点来回摇晃。
+    let mut contacts: Vec<Contact> = vertices
+        .iter()
+        .enumerate()
+        .filter(|(_, &vertex)| wall.depth(vertex) > penetration - config.linear_slop)
+        .map(|(i, &vertex)| Contact::new(vertex, i as u32))
+        .collect();
+    contacts.sort_by(|a, b| {
+        wall.depth(b.point)
+            .partial_cmp(&wall.depth(a.point))
+            .unwrap()
+    });
+    contacts.truncate(2);
+
+    Some(CollisionInfo {
+        penetration,
+        normal: wall.normal(),
+        contacts,
+    })
+}
+
+// 线段朝向物体的一侧，由 start 指向 end 的右侧为法线方向，幽灵顶点随端点一起交换。
+struct Edge {
+    start: Vec2,
+    end: Vec2,
+    previous: Option<Vec2>,
+    next: Option<Vec2>,
+}
+
+// 接触法线所在的范围：由本边负责；属于相邻的边（凸角处）；
+// 或位于凹角内、指向背面，只能沿本边的法线接触。
+enum NormalRegion {
+    Own,
+    Neighbour,
+    Blocked,
+}
+
+impl Edge {
+    // 双面的边翻转为朝向 point 的一侧；单面的边在 point 位于背面时返回 None。
+    fn facing(segment: &Segment, point: Vec2) -> Option<Self> {
+        let (start, end) = segment.vertices();
+        let (previous, next) = segment.ghost_vertices();
+        let edge = Self {
+            start,
+            end,
+            previous,
+            next,
+        };
+        if edge.normal() * (point - start) >= 0.0 {
+            Some(edge)
+        } else if segment.one_sided {
+            None
+        } else {
+            Some(Self {
+                start: end,
+                end: start,
+                previous: next,
+                next: previous,
+            })
+        }
+    }
+
+    fn normal(&self) -> Vec2 {
+        edge_normal(self.start, self.end)
+    }
+
+    // 以本边法线为零，转向 start 一侧的角度为正。凸角处两条边法线之间的范围由后一条边负责，
+    // 没有相邻边的一端不受限制。
+    fn classify(&self, normal: Vec2, tolerance: Float) -> NormalRegion {
+        let face_normal = self.normal();
+        if face_normal * normal < 0.0 {
+            return NormalRegion::Blocked;
+        }
+
+        let tangent = (self.end - self.start).normalize();
+        let angle = |normal: Vec2| (-(tangent * normal)).atan2(face_normal * normal);
+        let theta = angle(normal);
+        if theta.abs() <= tolerance {
+            return NormalRegion::Own;
+        }
+
+        if theta > 0.0 {
+            match self.previous {
+                None => NormalRegion::Own,
+                Some(previous) => {
+                    let limit = angle(edge_normal(previous, self.start));
+                    if limit <= tolerance {
+                        NormalRegion::Blocked
+                    } else if theta < limit {
+                        NormalRegion::Own
+                    } else {
+                        NormalRegion::Neighbour
+                    }
+                }
+            }
+        } else {
+            match self.next {
+                None => NormalRegion::Own,
+                Some(next) => {
+                    if -angle(edge_normal(self.end, next)) <= tolerance {
+                        NormalRegion::Blocked
+                    } else {
+                        NormalRegion::Neighbour
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn circle_contact(circle: &Circle, penetration: Float, normal: Vec2) -> CollisionInfo {
+    CollisionInfo {
+        penetration,
+        normal,
+        contacts: vec![Contact::new(
+            circle.rigid_body().position + normal * (circle.radius - penetration / 2.0),
+            0,
+        )],
+    }
+}
+
+// 分离轴定理：顶点须按逆时针顺序排列。
+fn detect_collision_convex(
+    vertices1: &[Vec2],
+    vertices2: &[Vec2],
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let (edge1, separation1) = find_max_separation(vertices1, vertices2);
+    if separation1 >= 0.0 {
+        return None;
+    }
+
+    let (edge2, separation2) = find_max_separation(vertices2, vertices1);
+    if separation2 >= 0.0 {
+        return None;
+    }
+
+    // 以分离距离较大的边作为参考边，略微偏向第一个多边形，避免两者相近时来回切换。
+    let (reference, incident, edge, flip) = if separation2 > separation1 + config.float_tolerance {
+        (vertices2, vertices1, edge2, true)
+    } else {
+        (vertices1, vertices2, edge1, false)
+    };
+
+    let v1 = reference[edge];
+    let v2 = reference[(edge + 1) % reference.len()];
+    let normal = edge_normal(v1, v2);
+    let clipped = clip_incident_edge(reference, incident, edge)?;
+
+    // 特征编号：参考多边形及其参考边，加上入射边的顶点或裁剪该点的参考边侧面。
+    let reference_feature = (flip as u32) << 31 | (edge as u32) << 16;
+    let mut penetration: Float = 0.0;
+    let mut contacts = Vec::new();
+    for contact in clipped {
+        let separation = normal * (contact.point - v1);
+        if separation < 0.0 {
+            penetration = penetration.max(-separation);
+            contacts.push(Contact::new(
+                contact.point,
+                reference_feature | contact.feature.0,
+            ));
+        }
+    }
+
+    if contacts.is_empty() {
+        return None;
+    }
+
+    Some(CollisionInfo {
+        penetration,
+        normal: if flip { -normal } else { normal },
+        contacts,
+    })
+}
+
+// 带半径的凸形状，如胶囊（核心为只有两个顶点的线段）。核心相交时同多边形一样裁剪入射边；
+// 核心相离时先求核心间的最近点，最近点连线与参考边法线不一致（端点区域）时只有一个接触点。
+// 接触点取两个表面的中点。
+fn detect_collision_rounded(
+    vertices1: &[Vec2],
+    radius1: Float,
+    vertices2: &[Vec2],
+    radius2: Float,
+    config: &SolverConfig,
+) -> Option<CollisionInfo> {
+    let radius = radius1 + radius2;
+    let (edge1, separation1) = find_max_separation(vertices1, vertices2);
+    if separation1 >= radius {
+        return None;
+    }
+
+    let (edge2, separation2) = find_max_separation(vertices2, vertices1);
+    if separation2 >= radius {
+        return None;
+    }
+
+    let (reference, incident, edge, flip) = if separation2 > separation1 + config.float_tolerance {
+        (vertices2, vertices1, edge2, true)
+    } else {
+        (vertices1, vertices2, edge1, false)
+    };
+    // 分离轴给出的只是核心间距离的下界，端点区域内的实际距离更大。
+    let core_separation = separation1
+        .max(separation2)
+        .max(find_end_separation(vertices1, vertices2))
+        .max(find_end_separation(vertices2, vertices1));
+    let closest = if core_separation > 0.0 {
+        let (point1, point2, edges) = find_closest_points(vertices1, vertices2);
+        let offset = point2 - point1;
+        let distance = offset.length();
+        if distance >= radius {
+            return None;
+        }
+
+        let closest_normal = offset / distance;
+        let point = (point1 + point2) / 2.0 + closest_normal * ((radius1 - radius2) / 2.0);
+        // 法线偏离参考边的法线时最近点位于顶点区域，只有这一个顶点接触，无需裁剪。
+        let face_normal = edge_normal(reference[edge], reference[(edge + 1) % reference.len()]);
+        let face_normal = if flip { -face_normal } else { face_normal };
+        if closest_normal * face_normal < 1.0 - config.float_tolerance {
+            let feature = find_closest_vertex(vertices1, vertices2, (point1, point2), edges);
+            return Some(CollisionInfo {
+                penetration: radius - distance,
+                normal: closest_normal,
+                contacts: vec![Contact::new(point, feature)],
+            });
+        }
+
+        // 面接触时仍由裁剪得到接触点，裁剪失败时退回最近点。
+        Some(CollisionInfo {
+            penetration: radius - distance,
+            normal: closest_normal,
+            contacts: vec![Contact::new(point, CLOSEST_POINTS)],
+        })
+    } else {
+        None
+    };
+
+    let (reference_radius, incident_radius) = if flip {
+        (radius2, radius1)
+    } else {
+        (radius1, radius2)
+    };
+    clip_rounded(
+        (reference, reference_radius),
+        (incident, incident_radius),
+        edge,
+        flip,
+    )
+    .or(closest)
+}
+
+// 以参考边裁剪入射边，接触点取两个表面的中点；flip 为 true 时法线由入射形状指向参考形状。
+fn clip_rounded(
+    (reference, reference_radius): (&[Vec2], Float),
+    (incident, incident_radius): (&[Vec2], Float),
+    edge: usize,
+    flip: bool,
+) -> Option<CollisionInfo> {
+    let v1 = reference[edge];
+    let v2 = reference[(edge + 1) % reference.len()];
+    let normal = edge_normal(v1, v2);
+    let clipped = clip_incident_edge(reference, incident, edge)?;
+
+    let radius = reference_radius + incident_radius;
+    let reference_feature = (flip as u32) << 31 | (edge as u32) << 16;
+    let mut penetration: Float = 0.0;
+    let mut contacts = Vec::new();
+    for contact in clipped {
+        let separation = normal * (contact.point - v1);
+        if separation < radius {
+            penetration = penetration.max(radius - separation);
+            contacts.push(Contact::new(
+                contact.point + normal * ((reference_radius - incident_radius - separation) / 2.0),
+                reference_feature | contact.feature.0,
+            ));
+        }
+    }
+
+    if contacts.is_empty() {
+        return None;
+    }
+
+    Some(CollisionInfo {
+        penetration,
+        normal: if flip { -normal } else { normal },
+        contacts,
+    })
+}
+
+// 用参考边的两个侧面裁剪入射边，返回的接触点以入射边的顶点或裁剪该点的侧面标记。
+fn clip_incident_edge(reference: &[Vec2], incident: &[Vec2], edge: usize) -> Option<Vec<Contact>> {
+    let v1 = reference[edge];
+    let v2 = reference[(edge + 1) % reference.len()];
+    let tangent = (v2 - v1).normalize();
+
+    let incident_edge = find_incident_edge(incident, edge_normal(v1, v2));
+    let incident_vertex = (incident_edge + 1) % incident.len();
+    let incident_points = [
+        Contact::new(incident[incident_edge], incident_edge as u32),
+        Contact::new(incident[incident_vertex], incident_vertex as u32),
+    ];
+
+    let clipped = clip_segment(
+        &incident_points,
+        -tangent,
+        -(tangent * v1),
+        CLIPPED_BY_SIDE1,
+    );
+    if clipped.len() < 2 {
+        return None;
+    }
+    let clipped = clip_segment(&clipped, tangent, tangent * v2, CLIPPED_BY_SIDE2);
+    if clipped.len() < 2 {
+        return None;
+    }
+
+    Some(clipped)
+}
+
+// 两个互不相交的凸多边形（或线段）间距离最近的一对点，分别位于两者的边上。
+// 返回两个最近点及其所在的边。
+fn find_closest_points(vertices1: &[Vec2], vertices2: &[Vec2]) -> (Vec2, Vec2, (usize, usize)) {
+    let mut closest = (vertices1[0], vertices2[0], (0, 0));
+    let mut min_distance_squared = Float::INFINITY;
+    for i in 0..vertices1.len() {
+        let (start1, end1) = (vertices1[i], vertices1[(i + 1) % vertices1.len()]);
+        for j in 0..vertices2.len() {
+            let (start2, end2) = (vertices2[j], vertices2[(j + 1) % vertices2.len()]);
+            let (point1, point2) = closest_points_on_segments(start1, end1, start2, end2);
+            let distance_squared = (point2 - point1).length_squared();
+            if distance_squared < min_distance_squared {
+                min_distance_squared = distance_squared;
+                closest = (point1, point2, (i, j));
+            }
+        }
+    }
+
+    closest
+}
+
+// 两条边之间的最近点中至少有一个是边的端点，取离对应最近点最近的端点；
+// 特征编号为该顶点所属的形状及其下标。
+fn find_closest_vertex(
+    vertices1: &[Vec2],
+    vertices2: &[Vec2],
+    (point1, point2): (Vec2, Vec2),
+    (edge1, edge2): (usize, usize),
+) -> u32 {
+    let candidates = [
+        (0, vertices1, edge1, point1),
+        (0, vertices1, edge1 + 1, point1),
+        (1, vertices2, edge2, point2),
+        (1, vertices2, edge2 + 1, point2),
+    ];
+    let (shape, vertex, _) = candidates
+        .iter()
+        .map(|&(shape, vertices, vertex, point)| {
+            let vertex = vertex % vertices.len();
+            (shape, vertex, (vertices[vertex] - point).length_squared())
+        })
+        .min_by(|x, y| x.2.partial_cmp(&y.2).unwrap())
+        .unwrap();
+
+    (shape as u32) << 31 | (vertex as u32) << 16 | CLOSEST_VERTEX
+}
+
+fn same_point(point1: Vec2, point2: Vec2, config: &SolverConfig) -> bool {
+    (point1 - point2).length_squared() <= config.float_tolerance * config.float_tolerance
+}
+
+fn closest_point_on_segment(point: Vec2, start: Vec2, end: Vec2) -> Vec2 {
+    let direction = end - start;
+    let length_squared = direction.length_squared();
+    if length_squared == 0.0 {
+        return start;
+    }
+
+    // 落在端点区域时返回端点本身，便于与端点比较。
+    let t = (point - start) * direction / length_squared;
+    if t <= 0.0 {
+        start
+    } else if t >= 1.0 {
+        end
+    } else {
+        start + direction * t
+    }
+}
+
+// 两条线段上距离最近的一对点；平行时取其中任意一对。
+fn closest_points_on_segments(start1: Vec2, end1: Vec2, start2: Vec2, end2: Vec2) -> (Vec2, Vec2) {
+    let direction1 = end1 - start1;
+    let direction2 = end2 - start2;
+    let offset = start1 - start2;
+    let length_squared1 = direction1.length_squared();
+    let length_squared2 = direction2.length_squared();
+    let clamp = |t: Float| t.clamp(0.0, 1.0);
+
+    if length_squared1 == 0.0 {
+        return (start1, closest_point_on_segment(start1, start2, end2));
+    }
+    if length_squared2 == 0.0 {
+        return (closest_point_on_segment(start2, start1, end1), start2);
+    }
+
+    let dot = direction1 * direction2;
+    let c = direction1 * offset;
+    let f = direction2 * offset;
+    let denominator = length_squared1 * length_squared2 - dot * dot;
+    let mut s = if denominator > 0.0 {
+        clamp((dot * f - c * length_squared2) / denominator)
+    } else {
+        0.0
+    };
+    let mut t = (dot * s + f) / length_squared2;
+    if t < 0.0 {
+        t = 0.0;
+        s = clamp(-c / length_squared1);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = clamp((dot - c) / length_squared1);
+    }
+
+    (start1 + direction1 * s, start2 + direction2 * t)
+}
+
+fn edge_normal(v1: Vec2, v2: Vec2) -> Vec2 {
+    -(v2 - v1).perp().normalize()
+}
+
+fn find_max_separation(vertices1: &[Vec2], vertices2: &[Vec2]) -> (usize, Float) {
+    let mut best_edge = 0;
+    let mut max_separation = Float::NEG_INFINITY;
+    for i in 0..vertices1.len() {
+        let v1 = vertices1[i];
+        let normal = edge_normal(v1, vertices1[(i + 1) % vertices1.len()]);
+        let separation = vertices2
+            .iter()
+            .map(|&v2| normal * (v2 - v1))
+            .fold(Float::INFINITY, Float::min);
+        if separation > max_separation {
+            max_separation = separation;
+            best_edge = i;
+        }
+    }
+
+    (best_edge, max_separation)
+}
+
+// 线段的两端可视为长度为 0 的边，其法线沿线段方向；多边形没有这样的边。
+fn find_end_separation(vertices1: &[Vec2], vertices2: &[Vec2]) -> Float {
+    if vertices1.len() != 2 {
+        return Float::NEG_INFINITY;
+    }
+
+    let (start, end) = (vertices1[0], vertices1[1]);
+    let direction = (end - start).normalize();
+    let separation = |normal: Vec2, vertex: Vec2| {
+        vertices2
+            .iter()
+            .map(|&v| normal * (v - vertex))
+            .fold(Float::INFINITY, Float::min)
+    };
+    separation(direction, end).max(separation(-direction, start))
+}
+
+fn find_incident_edge(vertices: &[Vec2], reference_normal: Vec2) -> usize {
+    let mut incident_edge = 0;
+    let mut min_dot = Float::INFINITY;
+    for i in 0..vertices.len() {
+        let normal = edge_normal(vertices[i], vertices[(i + 1) % vertices.len()]);
+        let dot = normal * reference_normal;
+        if dot < min_dot {
+            min_dot = dot;
+            incident_edge = i;
+        }
+    }
+
+    incident_edge
+}
+
+// 保留线段位于 normal * p <= offset 一侧的部分，裁剪出的新点以 feature 标记。
+fn clip_segment(points: &[Contact], normal: Vec2, offset: Float, feature: u32) -> Vec<Contact> {
+    let (p1, p2) = (points[0].point, points[1].point);
+    let distance1 = normal * p1 - offset;
+    let distance2 = normal * p2 - offset;
+
+    let mut result = Vec::new();
+    if distance1 <= 0.0 {
+        result.push(points[0]);
+    }
+    if distance2 <= 0.0 {
+        result.push(points[1]);
+    }
+    if distance1 * distance2 < 0.0 {
+        result.push(Contact::new(
+            p1 + (p2 - p1) * (distance1 / (distance1 - distance2)),
+            feature,
+        ));
+    }
+
+    result
 }
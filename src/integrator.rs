@@ -0,0 +1,122 @@
+use crate::{
+    algebra::{Float, Vec2},
+    shapes::{BodyType, RigidBody},
+};
+
+// 依赖物体状态（位置、速度）的外力，如弹簧与引力场，返回作用在质心上的力。
+pub type ForceField = dyn Fn(&RigidBody) -> Vec2;
+
+// ConstantAcceleration：视一帧内加速度不变，加速度恒定时结果精确，但在弹簧等力场中能量持续增长。
+// SemiImplicitEuler：先更新速度再更新位置，辛积分，振动系统的能量有界。
+// VelocityVerlet：用帧首与帧末加速度的平均值更新速度，二阶精度且为辛积分。
+// RungeKutta4：四阶精度，每帧计算四次力场，适合随位置剧烈变化的力场。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Integrator {
+    #[default]
+    ConstantAcceleration,
+    SemiImplicitEuler,
+    VelocityVerlet,
+    RungeKutta4,
+}
+
+// 累积的外力与力矩在一帧内视为不变，使用后清零；质量无穷大的物体不受重力与外力影响。
+// 静态物体不动，运动学物体按其速度匀速运动。
+pub(crate) fn integrate(
+    body: &mut RigidBody,
+    integrator: Integrator,
+    gravity: Vec2,
+    force_field: Option<&ForceField>,
+    duration: Float,
+) {
+    match body.body_type {
+        BodyType::Dynamic => integrate_dynamic(body, integrator, gravity, force_field, duration),
+        BodyType::Kinematic => {
+            body.position += body.velocity * duration;
+            body.angle += body.angular_velocity * duration;
+        }
+        BodyType::Static => {
+            body.velocity = Vec2::default();
+            body.angular_velocity = 0.0;
+        }
+    }
+    body.force = Vec2::default();
+    body.torque = 0.0;
+}
+
+fn integrate_dynamic(
+    body: &mut RigidBody,
+    integrator: Integrator,
+    gravity: Vec2,
+    force_field: Option<&ForceField>,
+    duration: Float,
+) {
+    let inverse_mass = body.inverse_mass();
+    let is_driven = inverse_mass > 0.0;
+    let constant_acceleration = if is_driven {
+        body.acceleration + gravity + body.force * inverse_mass
+    } else {
+        body.acceleration
+    };
+    let mut state = *body;
+    let mut acceleration = |position: Vec2, velocity: Vec2| match force_field {
+        Some(force_field) if is_driven => {
+            state.position = position;
+            state.velocity = velocity;
+            constant_acceleration + force_field(&state) * inverse_mass
+        }
+        _ => constant_acceleration,
+    };
+
+    let (position, velocity) = (body.position, body.velocity);
+    let (position, velocity) = match integrator {
+        Integrator::ConstantAcceleration => {
+            let a = acceleration(position, velocity);
+            (
+                position + velocity * duration + a * (duration * duration) / 2.0,
+                velocity + a * duration,
+            )
+        }
+        Integrator::SemiImplicitEuler => {
+            let velocity = velocity + acceleration(position, velocity) * duration;
+            (position + velocity * duration, velocity)
+        }
+        Integrator::VelocityVerlet => {
+            let a0 = acceleration(position, velocity);
+            let position = position + velocity * duration + a0 * (duration * duration) / 2.0;
+            // 帧末速度未知，力场依赖速度时以显式估计代替。
+            let a1 = acceleration(position, velocity + a0 * duration);
+            (position, velocity + (a0 + a1) * (duration / 2.0))
+        }
+        Integrator::RungeKutta4 => {
+            let half = duration / 2.0;
+            let (x1, v1) = (position, velocity);
+            let a1 = acceleration(x1, v1);
+            let (x2, v2) = (position + v1 * half, velocity + a1 * half);
+            let a2 = acceleration(x2, v2);
+            let (x3, v3) = (position + v2 * half, velocity + a2 * half);
+            let a3 = acceleration(x3, v3);
+            let (x4, v4) = (position + v3 * duration, velocity + a3 * duration);
+            let a4 = acceleration(x4, v4);
+            (
+                position + (v1 + v2 * 2.0 + v3 * 2.0 + v4) * (duration / 6.0),
+                velocity + (a1 + a2 * 2.0 + a3 * 2.0 + a4) * (duration / 6.0),
+            )
+        }
+    };
+    body.position = position;
+    body.velocity = velocity;
+
+    // 力矩在一帧内不变，除半隐式欧拉外均按匀加速转动精确积分。
+    let angular_acceleration = body.torque * body.inverse_inertia();
+    match integrator {
+        Integrator::SemiImplicitEuler => {
+            body.angular_velocity += angular_acceleration * duration;
+            body.angle += body.angular_velocity * duration;
+        }
+        _ => {
+            body.angle += body.angular_velocity * duration
+                + angular_acceleration * (duration * duration) / 2.0;
+            body.angular_velocity += angular_acceleration * duration;
+        }
+    }
+}
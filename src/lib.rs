@@ -1,10 +1,16 @@
+pub mod algebra;
 pub mod box2d;
+pub mod detection_broad_phase;
 pub mod shapes;
 
-mod algebra;
+mod body_set;
 mod collide_calculation;
-mod detection_broad_phase;
+mod contact_solver;
+mod detection_continuous;
 mod detection_narrow_phase;
+mod integrator;
+
+pub use detection_narrow_phase::{CollisionInfo, Contact, ContactFeature};
 
 #[cfg(test)]
 mod tests;
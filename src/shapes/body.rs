@@ -0,0 +1,243 @@
+use super::{
+    Bounded, CollisionFilter, MassLike, MassProperties, Material, MaterialLike, RigidBody,
+    RigidBodyLike,
+};
+use crate::{
+    algebra::{Float, Mat22, Vec2},
+    body_set::BodyHandle,
+};
+
+// 附着在刚体上的形状。offset 与 angle 为相对于刚体原点的局部位姿，构造时取自形状自身的刚体；
+// 加入刚体后形状的位姿与速度在每帧检测前由刚体同步。
+pub struct Fixture<T> {
+    pub shape: T,
+    pub offset: Vec2,
+    pub angle: Float,
+    pub filter: CollisionFilter,
+    // 显式设置的质量属性，质心为相对于形状位置的局部坐标；为 None 时由形状的密度算出。
+    pub mass_override: Option<MassProperties>,
+    // 宽检测中的代理，由 World 在插入刚体后分配。
+    pub(crate) proxy: Option<BodyHandle>,
+}
+
+impl<T> Fixture<T>
+where
+    T: RigidBodyLike,
+{
+    pub fn new(shape: T) -> Self {
+        let transform = shape.rigid_body().transform();
+        Self {
+            shape,
+            offset: transform.position,
+            angle: transform.angle,
+            filter: CollisionFilter::default(),
+            mass_override: None,
+            proxy: None,
+        }
+    }
+}
+
+impl<T> Fixture<T>
+where
+    T: MassLike,
+{
+    // 质心为相对于刚体原点的局部坐标，转动惯量绕质心。
+    pub fn mass_properties(&self) -> MassProperties {
+        let properties = self
+            .mass_override
+            .unwrap_or_else(|| self.shape.mass_properties());
+        MassProperties {
+            center: self.offset + Mat22::rotation(self.angle) * properties.center,
+            ..properties
+        }
+    }
+}
+
+impl<T> MaterialLike for Fixture<T>
+where
+    T: MaterialLike,
+{
+    fn material_mut(&mut self) -> &mut Material {
+        self.shape.material_mut()
+    }
+
+    fn material(&self) -> &Material {
+        self.shape.material()
+    }
+}
+
+// 由若干夹具组成的刚体。rigid_body 的位置为质心，积分与求解都围绕质心进行；
+// local_center 为质心相对于刚体原点的局部坐标。
+pub struct Body<T> {
+    rigid_body: RigidBody,
+    local_center: Vec2,
+    fixtures: Vec<Fixture<T>>,
+}
+
+impl<T> Body<T> {
+    // rigid_body 的位置为刚体原点，加入夹具后质心随之移动。
+    pub fn new(rigid_body: RigidBody) -> Self {
+        Self {
+            rigid_body,
+            local_center: Vec2::default(),
+            fixtures: Vec::new(),
+        }
+    }
+
+    pub fn fixtures(&self) -> &[Fixture<T>] {
+        &self.fixtures
+    }
+
+    // 修改夹具的位姿、尺寸或密度后需调用 reset_mass_data。
+    pub fn fixtures_mut(&mut self) -> &mut [Fixture<T>] {
+        &mut self.fixtures
+    }
+
+    // 为所有夹具设置同一个碰撞过滤器。
+    pub fn set_filter(&mut self, filter: CollisionFilter) {
+        for fixture in &mut self.fixtures {
+            fixture.filter = filter;
+        }
+    }
+
+    pub fn local_center(&self) -> Vec2 {
+        self.local_center
+    }
+
+    pub fn origin(&self) -> Vec2 {
+        self.rigid_body.position - Mat22::rotation(self.rigid_body.angle) * self.local_center
+    }
+}
+
+impl<T> Body<T>
+where
+    T: MassLike,
+{
+    // 单个形状组成的刚体，形状的刚体即为整体的刚体，夹具位于原点；
+    // 质量属性由形状的密度计算，密度得出的质量为 0 时保留形状给定的质量与转动惯量。
+    pub fn from_shape(shape: T) -> Self {
+        let mut body = Self::new(*shape.rigid_body());
+        let mut fixture = Fixture::new(shape);
+        fixture.offset = Vec2::default();
+        fixture.angle = 0.0;
+        body.fixtures.push(fixture);
+        body.reset_mass_data();
+        body.synchronize_fixtures();
+        body
+    }
+
+    pub fn add_fixture(&mut self, fixture: Fixture<T>) {
+        self.fixtures.push(fixture);
+        self.reset_mass_data();
+    }
+
+    // 由各夹具合成质量、质心与转动惯量（平行轴定理）。
+    // 夹具的质量总和为 0 时保留刚体原有的质量与转动惯量，质心位于原点。
+    pub fn reset_mass_data(&mut self) {
+        let fixtures: Vec<MassProperties> =
+            self.fixtures.iter().map(Fixture::mass_properties).collect();
+        let mass: Float = fixtures.iter().map(|fixture| fixture.mass).sum();
+        if mass <= 0.0 {
+            self.set_mass_data(MassProperties {
+                mass: self.rigid_body.mass,
+                center: Vec2::default(),
+                inertia: self.rigid_body.inertia,
+            });
+            return;
+        }
+
+        let center = fixtures.iter().fold(Vec2::default(), |sum, fixture| {
+            sum + fixture.center * fixture.mass
+        }) / mass;
+        let inertia = fixtures
+            .iter()
+            .map(|fixture| {
+                fixture.inertia + fixture.mass * (fixture.center - center).length_squared()
+            })
+            .sum();
+        self.set_mass_data(MassProperties {
+            mass,
+            center,
+            inertia,
+        });
+    }
+}
+
+impl<T> Body<T>
+where
+    T: RigidBodyLike,
+{
+    // 显式设置质量属性，质心为相对于刚体原点的局部坐标；再次加入夹具或调用 reset_mass_data 前保持不变。
+    // 原点保持不动，质心的速度随之修正。
+    pub fn set_mass_data(&mut self, properties: MassProperties) {
+        self.rigid_body.mass = properties.mass;
+        self.rigid_body.inertia = properties.inertia;
+
+        let rotation = Mat22::rotation(self.rigid_body.angle);
+        let shift = rotation * (properties.center - self.local_center);
+        self.rigid_body.position += shift;
+        self.rigid_body.velocity += Vec2::scalar_cross(self.rigid_body.angular_velocity, shift);
+        self.local_center = properties.center;
+        self.synchronize_fixtures();
+    }
+
+    // 将各夹具形状的位姿与速度更新为世界坐标下的值。
+    pub fn synchronize_fixtures(&mut self) {
+        let body = self.rigid_body;
+        let rotation = Mat22::rotation(body.angle);
+        for fixture in &mut self.fixtures {
+            let position = body.position + rotation * (fixture.offset - self.local_center);
+            let shape = fixture.shape.rigid_body_mut();
+            shape.position = position;
+            shape.angle = body.angle + fixture.angle;
+            shape.velocity = body.velocity_at(position);
+            shape.angular_velocity = body.angular_velocity;
+            shape.body_type = body.body_type;
+            shape.bullet = body.bullet;
+        }
+    }
+}
+
+impl<T> RigidBodyLike for Body<T> {
+    fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        &mut self.rigid_body
+    }
+
+    fn rigid_body(&self) -> &RigidBody {
+        &self.rigid_body
+    }
+}
+
+// 各夹具包围盒的并集。
+impl<T> Bounded for Body<T>
+where
+    T: Bounded,
+{
+    fn bound_left(&self) -> Float {
+        self.fixtures
+            .iter()
+            .map(|fixture| fixture.shape.bound_left())
+            .fold(Float::INFINITY, Float::min)
+    }
+
+    fn bound_top(&self) -> Float {
+        self.fixtures
+            .iter()
+            .map(|fixture| fixture.shape.bound_top())
+            .fold(Float::NEG_INFINITY, Float::max)
+    }
+
+    fn bound_right(&self) -> Float {
+        self.fixtures
+            .iter()
+            .map(|fixture| fixture.shape.bound_right())
+            .fold(Float::NEG_INFINITY, Float::max)
+    }
+
+    fn bound_bottom(&self) -> Float {
+        self.fixtures
+            .iter()
+            .map(|fixture| fixture.shape.bound_bottom())
+            .fold(Float::INFINITY, Float::min)
+    }
+}
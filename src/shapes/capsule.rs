@@ -0,0 +1,195 @@
+use std::f64::consts::PI;
+
+use super::{
+    Bounded, Circle, Collider, MassLike, MassProperties, Material, MaterialLike, Polygon,
+    Rectangle, RigidBody, RigidBodyLike, Segment, Wall,
+};
+use crate::{
+    algebra::{Float, Mat22, Vec2},
+    collide_calculation::{
+        collide_capsule_and_capsule, collide_capsule_and_polygon, collide_capsule_and_rectangle,
+        collide_circle_and_capsule, collide_segment_and_capsule, collide_wall_and_capsule,
+    },
+    contact_solver::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_capsule_and_capsule, detect_collision_capsule_and_polygon,
+        detect_collision_capsule_and_rectangle, detect_collision_circle_and_capsule,
+        detect_collision_segment_and_capsule, detect_collision_wall_and_capsule, CollisionInfo,
+    },
+};
+
+// 胶囊：以刚体位置为中点、沿局部 x 轴、长为 length 的线段，向外扩张 radius。
+pub struct Capsule {
+    material: Material,
+    rigid_body: RigidBody,
+    pub length: Float,
+    pub radius: Float,
+}
+
+impl Capsule {
+    // 质量取自 rigid_body，转动惯量由质量与尺寸算出。
+    pub fn new(
+        material: Material,
+        mut rigid_body: RigidBody,
+        length: Float,
+        radius: Float,
+    ) -> Self {
+        rigid_body.inertia = rigid_body.mass * unit_inertia(length, radius);
+
+        Self {
+            material,
+            rigid_body,
+            length,
+            radius,
+        }
+    }
+
+    // 线段的两个端点，即两端半圆的圆心。
+    pub fn segment(&self) -> (Vec2, Vec2) {
+        let half = Mat22::rotation(self.rigid_body.angle) * Vec2::new(self.length / 2.0, 0.0);
+        let position = self.rigid_body.position;
+        (position - half, position + half)
+    }
+
+    pub fn area(&self) -> Float {
+        area(self.length, self.radius)
+    }
+
+    fn bound_half_extent(&self) -> Vec2 {
+        let (_, end) = self.segment();
+        let half = end - self.rigid_body.position;
+        Vec2::new(half.x.abs() + self.radius, half.y.abs() + self.radius)
+    }
+}
+
+fn area(length: Float, radius: Float) -> Float {
+    2.0 * radius * length + PI * radius * radius
+}
+
+// 单位质量下绕中心的转动惯量：中间的矩形加上两端的半圆，半圆按平行轴定理移至中心。
+fn unit_inertia(length: Float, radius: Float) -> Float {
+    let rect_area = 2.0 * radius * length;
+    let circle_area = PI * radius * radius;
+    let area = rect_area + circle_area;
+    if area <= 0.0 {
+        return 0.0;
+    }
+
+    let rect_inertia = rect_area * (length * length + 4.0 * radius * radius) / 12.0;
+    let half_length = length / 2.0;
+    let centroid_offset = 4.0 * radius / (3.0 * PI);
+    let circle_inertia = circle_area
+        * (radius * radius / 2.0 + half_length * half_length + 2.0 * half_length * centroid_offset);
+    (rect_inertia + circle_inertia) / area
+}
+
+impl Bounded for Capsule {
+    fn bound_left(&self) -> Float {
+        self.rigid_body.position.x - self.bound_half_extent().x
+    }
+
+    fn bound_top(&self) -> Float {
+        self.rigid_body.position.y + self.bound_half_extent().y
+    }
+
+    fn bound_right(&self) -> Float {
+        self.rigid_body.position.x + self.bound_half_extent().x
+    }
+
+    fn bound_bottom(&self) -> Float {
+        self.rigid_body.position.y - self.bound_half_extent().y
+    }
+}
+
+impl Collider for Capsule {
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig) {
+        other.collide_with_capsule(self, config);
+    }
+
+    fn collide_with_wall(&mut self, body: &Wall, config: &SolverConfig) {
+        collide_wall_and_capsule(body, self, config);
+    }
+
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig) {
+        collide_circle_and_capsule(body, self, config);
+    }
+
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig) {
+        collide_capsule_and_rectangle(self, body, config);
+    }
+
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig) {
+        collide_capsule_and_polygon(self, body, config);
+    }
+
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig) {
+        collide_capsule_and_capsule(self, body, config);
+    }
+
+    fn collide_with_segment(&mut self, body: &mut Segment, config: &SolverConfig) {
+        collide_segment_and_capsule(body, self, config);
+    }
+
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo> {
+        other.detect_with_capsule(self, config)
+    }
+
+    fn detect_with_wall(&self, body: &Wall, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_wall_and_capsule(body, self, config)
+    }
+
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_circle_and_capsule(body, self, config)
+    }
+
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        detect_collision_capsule_and_rectangle(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_capsule_and_polygon(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_capsule_and_capsule(body, self, config)
+    }
+
+    fn detect_with_segment(&self, body: &Segment, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_segment_and_capsule(body, self, config)
+    }
+}
+
+impl MassLike for Capsule {
+    fn compute_mass(&self, density: Float) -> MassProperties {
+        let mass = density * self.area();
+        MassProperties {
+            mass,
+            center: Vec2::default(),
+            inertia: mass * unit_inertia(self.length, self.radius),
+        }
+    }
+}
+
+impl RigidBodyLike for Capsule {
+    fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        &mut self.rigid_body
+    }
+
+    fn rigid_body(&self) -> &RigidBody {
+        &self.rigid_body
+    }
+}
+
+impl MaterialLike for Capsule {
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
@@ -0,0 +1,127 @@
+use std::{error::Error, fmt};
+
+use super::{Material, RigidBody, RigidBodyLike, Segment};
+use crate::algebra::{Float, Vec2, FLOADT_TOLERANCE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainError {
+    TooFewVertices,
+    Degenerate,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::TooFewVertices => write!(f, "the chain has too few vertices"),
+            ChainError::Degenerate => write!(f, "the chain has repeated adjacent vertices"),
+        }
+    }
+}
+
+impl Error for ChainError {}
+
+// 首尾相接的静态边组成的折线或闭合环，顶点为相对于刚体位置的局部坐标。
+// 链条由 segments 拆分为各条线段，作为同一个刚体的夹具插入 Box，
+// 相邻线段的另一端作为幽灵顶点；one_sided 的含义与 Segment 相同。
+pub struct Chain {
+    material: Material,
+    rigid_body: RigidBody,
+    vertices: Vec<Vec2>,
+    closed: bool,
+    pub one_sided: bool,
+}
+
+impl Chain {
+    // 折线至少需要两个顶点，两端没有幽灵顶点。
+    pub fn polyline(
+        material: Material,
+        rigid_body: RigidBody,
+        vertices: Vec<Vec2>,
+    ) -> Result<Self, ChainError> {
+        Self::with_tolerance(material, rigid_body, vertices, false, FLOADT_TOLERANCE)
+    }
+
+    // 闭合环至少需要三个顶点，末顶点与首顶点之间自动连接。
+    pub fn closed(
+        material: Material,
+        rigid_body: RigidBody,
+        vertices: Vec<Vec2>,
+    ) -> Result<Self, ChainError> {
+        Self::with_tolerance(material, rigid_body, vertices, true, FLOADT_TOLERANCE)
+    }
+
+    // 短于 tolerance 的边视为重复顶点，长度单位与默认不同时应传入 SolverConfig::float_tolerance。
+    pub fn with_tolerance(
+        material: Material,
+        rigid_body: RigidBody,
+        vertices: Vec<Vec2>,
+        closed: bool,
+        tolerance: Float,
+    ) -> Result<Self, ChainError> {
+        if vertices.len() < if closed { 3 } else { 2 } {
+            return Err(ChainError::TooFewVertices);
+        }
+
+        let count = vertices.len();
+        let edges = if closed { count } else { count - 1 };
+        let edge = |i: usize| vertices[(i + 1) % count] - vertices[i];
+        if (0..edges).any(|i| edge(i).length() < tolerance) {
+            return Err(ChainError::Degenerate);
+        }
+
+        Ok(Self {
+            material,
+            rigid_body,
+            vertices,
+            closed,
+            one_sided: false,
+        })
+    }
+
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn segments(&self) -> Vec<Segment> {
+        let count = self.vertices.len();
+        let vertex = |i: isize| {
+            if self.closed {
+                Some(self.vertices[i.rem_euclid(count as isize) as usize])
+            } else if 0 <= i && i < count as isize {
+                Some(self.vertices[i as usize])
+            } else {
+                None
+            }
+        };
+
+        let edges = if self.closed { count } else { count - 1 };
+        (0..edges as isize)
+            .map(|i| {
+                let mut segment = Segment::new(
+                    self.material,
+                    self.rigid_body,
+                    vertex(i).unwrap(),
+                    vertex(i + 1).unwrap(),
+                );
+                segment.previous = vertex(i - 1);
+                segment.next = vertex(i + 2);
+                segment.one_sided = self.one_sided;
+                segment
+            })
+            .collect()
+    }
+}
+
+impl RigidBodyLike for Chain {
+    fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        &mut self.rigid_body
+    }
+
+    fn rigid_body(&self) -> &RigidBody {
+        &self.rigid_body
+    }
+}
@@ -1,8 +1,20 @@
-use super::{Bounded, Collider, Material, MaterialLike, Rectangle, RigidBody, RigidBodyLike, Wall};
+use std::f64::consts::PI;
+
+use super::{
+    Bounded, Capsule, Collider, MassLike, MassProperties, Material, MaterialLike, Polygon,
+    Rectangle, RigidBody, RigidBodyLike, Segment, Wall,
+};
 use crate::{
-    algebra::Float,
+    algebra::{Float, Vec2},
     collide_calculation::{
-        collide_circle_and_circle, collide_circle_and_rectangle, collide_wall_and_circle,
+        collide_circle_and_capsule, collide_circle_and_circle, collide_circle_and_polygon,
+        collide_circle_and_rectangle, collide_segment_and_circle, collide_wall_and_circle,
+    },
+    contact_solver::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_circle_and_capsule, detect_collision_circle_and_circle,
+        detect_collision_circle_and_polygon, detect_collision_circle_and_rectangle,
+        detect_collision_segment_and_circle, detect_collision_wall_and_circle, CollisionInfo,
     },
 };
 
@@ -13,7 +25,10 @@ pub struct Circle {
 }
 
 impl Circle {
-    pub fn new(material: Material, rigid_body: RigidBody, radius: Float) -> Self {
+    // 质量取自 rigid_body，转动惯量由质量与半径算出。
+    pub fn new(material: Material, mut rigid_body: RigidBody, radius: Float) -> Self {
+        rigid_body.inertia = rigid_body.mass * radius * radius / 2.0;
+
         Self {
             material,
             rigid_body,
@@ -41,20 +56,75 @@ impl Bounded for Circle {
 }
 
 impl Collider for Circle {
-    fn collide_with(&mut self, other: &mut impl Collider) {
-        other.collide_with_circle(self);
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig) {
+        other.collide_with_circle(self, config);
+    }
+
+    fn collide_with_wall(&mut self, body: &Wall, config: &SolverConfig) {
+        collide_wall_and_circle(body, self, config);
+    }
+
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig) {
+        collide_circle_and_circle(self, body, config);
+    }
+
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig) {
+        collide_circle_and_rectangle(self, body, config);
+    }
+
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig) {
+        collide_circle_and_polygon(self, body, config);
+    }
+
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig) {
+        collide_circle_and_capsule(self, body, config);
     }
 
-    fn collide_with_wall(&mut self, body: &Wall) {
-        collide_wall_and_circle(body, self);
+    fn collide_with_segment(&mut self, body: &mut Segment, config: &SolverConfig) {
+        collide_segment_and_circle(body, self, config);
     }
 
-    fn collide_with_circle(&mut self, body: &mut Circle) {
-        collide_circle_and_circle(self, body);
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo> {
+        other.detect_with_circle(self, config)
     }
 
-    fn collide_with_rectangle(&mut self, body: &mut Rectangle) {
-        collide_circle_and_rectangle(self, body);
+    fn detect_with_wall(&self, body: &Wall, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_wall_and_circle(body, self, config)
+    }
+
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_circle_and_circle(body, self, config)
+    }
+
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        detect_collision_circle_and_rectangle(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_circle_and_polygon(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_circle_and_capsule(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_segment(&self, body: &Segment, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_segment_and_circle(body, self, config)
+    }
+}
+
+impl MassLike for Circle {
+    fn compute_mass(&self, density: Float) -> MassProperties {
+        let mass = density * PI * self.radius * self.radius;
+        MassProperties {
+            mass,
+            center: Vec2::default(),
+            inertia: mass * self.radius * self.radius / 2.0,
+        }
     }
 }
 
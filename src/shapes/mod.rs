@@ -1,4 +1,8 @@
-use crate::algebra::{Float, Vec2};
+use crate::{
+    algebra::{Float, Vec2},
+    contact_solver::SolverConfig,
+    detection_narrow_phase::CollisionInfo,
+};
 
 #[cfg(test)]
 mod tests;
@@ -9,9 +13,27 @@ pub use self::circle::Circle;
 mod rectangle;
 pub use self::rectangle::Rectangle;
 
+mod polygon;
+pub use self::polygon::{Polygon, PolygonError};
+
+mod shape;
+pub use self::shape::Shape;
+
 mod wall;
 pub use self::wall::Wall;
 
+mod capsule;
+pub use self::capsule::Capsule;
+
+mod segment;
+pub use self::segment::Segment;
+
+mod chain;
+pub use self::chain::{Chain, ChainError};
+
+mod body;
+pub use self::body::{Body, Fixture};
+
 #[derive(Debug, Clone, Copy)]
 pub enum Orientation {
     Left,
@@ -28,18 +50,69 @@ pub trait Bounded {
 }
 
 pub trait Collider {
-    fn collide_with(&mut self, other: &mut impl Collider);
-    fn collide_with_wall(&mut self, body: &Wall);
-    fn collide_with_circle(&mut self, body: &mut Circle);
-    fn collide_with_rectangle(&mut self, body: &mut Rectangle);
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig);
+    fn collide_with_wall(&mut self, body: &Wall, config: &SolverConfig);
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig);
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig);
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig);
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig);
+    fn collide_with_segment(&mut self, body: &mut Segment, config: &SolverConfig);
+
+    // 仅检测碰撞而不处理：detect_with 返回的法线由 self 指向 other，
+    // 其余方法返回的法线由参数指向 self。
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo>;
+    fn detect_with_wall(&self, body: &Wall, config: &SolverConfig) -> Option<CollisionInfo>;
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo>;
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo>;
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo>;
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo>;
+    fn detect_with_segment(&self, body: &Segment, config: &SolverConfig) -> Option<CollisionInfo>;
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Transform {
+    pub position: Vec2,
+    pub angle: Float,
+}
+
+impl Transform {
+    pub fn lerp(self, other: Self, t: Float) -> Self {
+        Self {
+            position: self.position.lerp(other.position, t),
+            angle: self.angle + (other.angle - self.angle) * t,
+        }
+    }
+}
+
+// Static：始终静止；Kinematic：按设置的速度运动，推动动态物体而不受其影响；
+// 两者都不受重力、外力与碰撞的影响，视为质量无穷大。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyType {
+    #[default]
+    Dynamic,
+    Static,
+    Kinematic,
+}
+
+// bullet 为 true 时启用连续碰撞检测，避免高速运动的物体在两帧之间穿过其他物体。
+// force 与 torque 为本帧累积的外力与力矩，积分时使用后清零。
+#[derive(Debug, Clone, Copy, Default)]
 pub struct RigidBody {
     pub mass: Float,
+    pub inertia: Float,
     pub position: Vec2,
+    pub angle: Float,
     pub velocity: Vec2,
+    pub angular_velocity: Float,
     pub acceleration: Vec2,
+    pub force: Vec2,
+    pub torque: Float,
+    pub bullet: bool,
+    pub body_type: BodyType,
 }
 
 impl RigidBody {
@@ -49,16 +122,72 @@ impl RigidBody {
             position,
             velocity,
             acceleration,
+            ..Default::default()
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        Transform {
+            position: self.position,
+            angle: self.angle,
         }
     }
 
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.position = transform.position;
+        self.angle = transform.angle;
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        self.body_type == BodyType::Dynamic
+    }
+
+    // 质量不为正的动态物体同样视为质量无穷大。
     pub fn inverse_mass(&self) -> Float {
-        if self.mass > 0.0 {
+        if self.is_dynamic() && self.mass > 0.0 {
             1.0 / self.mass
         } else {
-            Float::INFINITY
+            0.0
+        }
+    }
+
+    // 转动惯量为 0 时视为不可转动。
+    pub fn inverse_inertia(&self) -> Float {
+        if self.is_dynamic() && self.inertia > 0.0 {
+            1.0 / self.inertia
+        } else {
+            0.0
         }
     }
+
+    // 任何冲量都不能改变其速度。
+    pub fn is_immovable(&self) -> bool {
+        self.inverse_mass() == 0.0 && self.inverse_inertia() == 0.0
+    }
+
+    pub fn apply_force(&mut self, force: Vec2) {
+        self.force += force;
+    }
+
+    // 作用点不在质心时同时产生力矩。
+    pub fn apply_force_at_point(&mut self, force: Vec2, point: Vec2) {
+        self.force += force;
+        self.torque += (point - self.position).cross(force);
+    }
+
+    pub fn apply_torque(&mut self, torque: Float) {
+        self.torque += torque;
+    }
+
+    // 冲量立即改变速度，不经过累积。
+    pub fn apply_impulse(&mut self, impulse: Vec2, point: Vec2) {
+        self.velocity += impulse * self.inverse_mass();
+        self.angular_velocity += (point - self.position).cross(impulse) * self.inverse_inertia();
+    }
+
+    pub fn velocity_at(&self, point: Vec2) -> Vec2 {
+        self.velocity + Vec2::scalar_cross(self.angular_velocity, point - self.position)
+    }
 }
 
 pub trait RigidBodyLike {
@@ -66,12 +195,71 @@ pub trait RigidBodyLike {
     fn rigid_body(&self) -> &RigidBody;
 }
 
-#[derive(Debug, Clone, Copy)]
+// 静摩擦系数决定接触点能否保持相对静止，超出后按动摩擦系数滑动。
+// density 为单位面积的质量，夹具与 reset_mass_data 据此算出质量与转动惯量。
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Material {
     pub restitution: Float,
+    pub static_friction: Float,
+    pub dynamic_friction: Float,
+    pub density: Float,
 }
 
 pub trait MaterialLike {
     fn material_mut(&mut self) -> &mut Material;
     fn material(&self) -> &Material;
 }
+
+// center 为质心相对于形状位置的局部坐标，inertia 为绕质心的转动惯量。
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MassProperties {
+    pub mass: Float,
+    pub center: Vec2,
+    pub inertia: Float,
+}
+
+pub trait MassLike: RigidBodyLike + MaterialLike {
+    // 给定密度下由几何形状算出的质量属性。
+    fn compute_mass(&self, density: Float) -> MassProperties;
+
+    fn mass_properties(&self) -> MassProperties {
+        self.compute_mass(self.material().density)
+    }
+
+    // 按材料的密度重新计算刚体的质量与转动惯量，覆盖构造时给定的值。
+    fn reset_mass_data(&mut self) {
+        let properties = self.mass_properties();
+        let body = self.rigid_body_mut();
+        body.mass = properties.mass;
+        body.inertia = properties.inertia;
+    }
+}
+
+// category_bits 为自身所属的类别，mask_bits 为会与之碰撞的类别，双方互相接受时才会碰撞。
+// group_index 相同且不为 0 时不再比较类别：为正总是碰撞，为负从不碰撞。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionFilter {
+    pub category_bits: u16,
+    pub mask_bits: u16,
+    pub group_index: i16,
+}
+
+impl Default for CollisionFilter {
+    fn default() -> Self {
+        Self {
+            category_bits: 0x0001,
+            mask_bits: 0xFFFF,
+            group_index: 0,
+        }
+    }
+}
+
+impl CollisionFilter {
+    pub fn should_collide(&self, other: &CollisionFilter) -> bool {
+        if self.group_index == other.group_index && self.group_index != 0 {
+            return self.group_index > 0;
+        }
+
+        self.mask_bits & other.category_bits != 0 && other.mask_bits & self.category_bits != 0
+    }
+}
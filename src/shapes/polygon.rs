@@ -0,0 +1,261 @@
+use std::{error::Error, fmt};
+
+use super::{
+    Bounded, Capsule, Circle, Collider, MassLike, MassProperties, Material, MaterialLike,
+    Rectangle, RigidBody, RigidBodyLike, Segment, Wall,
+};
+use crate::{
+    algebra::{Float, Mat22, Vec2, FLOADT_TOLERANCE},
+    collide_calculation::{
+        collide_capsule_and_polygon, collide_circle_and_polygon, collide_polygon_and_polygon,
+        collide_rectangle_and_polygon, collide_segment_and_polygon, collide_wall_and_polygon,
+    },
+    contact_solver::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_capsule_and_polygon, detect_collision_circle_and_polygon,
+        detect_collision_polygon_and_polygon, detect_collision_rectangle_and_polygon,
+        detect_collision_segment_and_polygon, detect_collision_wall_and_polygon, CollisionInfo,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonError {
+    TooFewVertices,
+    Degenerate,
+    NotConvex,
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices => write!(f, "a polygon needs at least three vertices"),
+            PolygonError::Degenerate => write!(f, "the polygon has no area or repeated vertices"),
+            PolygonError::NotConvex => write!(f, "the polygon is not strictly convex"),
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+pub struct Polygon {
+    material: Material,
+    rigid_body: RigidBody,
+    vertices: Vec<Vec2>,
+}
+
+impl Polygon {
+    // 顶点为相对于刚体位置的局部坐标，顺时针给出时会被反转为逆时针。
+    // 构造后刚体的位置移至多边形的质心；质量取自 rigid_body，转动惯量由质量与顶点算出。
+    pub fn new(
+        material: Material,
+        rigid_body: RigidBody,
+        vertices: Vec<Vec2>,
+    ) -> Result<Self, PolygonError> {
+        Self::with_tolerance(material, rigid_body, vertices, FLOADT_TOLERANCE)
+    }
+
+    // tolerance 为长度：短于它的边视为重复顶点，与前一条边所在直线的距离不超过它的顶点视为共线。
+    // 长度单位与默认不同时应传入 SolverConfig::float_tolerance。
+    pub fn with_tolerance(
+        material: Material,
+        mut rigid_body: RigidBody,
+        mut vertices: Vec<Vec2>,
+        tolerance: Float,
+    ) -> Result<Self, PolygonError> {
+        if vertices.len() < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+
+        let (area, centroid) = compute_area_and_centroid(&vertices);
+        if area.abs() < tolerance * tolerance {
+            return Err(PolygonError::Degenerate);
+        }
+        if area < 0.0 {
+            vertices.reverse();
+        }
+
+        let count = vertices.len();
+        let edge = |i: usize| vertices[(i + 1) % count] - vertices[i % count];
+        if (0..count).any(|i| edge(i).length() < tolerance) {
+            return Err(PolygonError::Degenerate);
+        }
+        if (0..count).any(|i| edge(i).cross(edge(i + 1)) <= tolerance * edge(i).length()) {
+            return Err(PolygonError::NotConvex);
+        }
+
+        let vertices: Vec<Vec2> = vertices.iter().map(|&vertex| vertex - centroid).collect();
+        rigid_body.position += Mat22::rotation(rigid_body.angle) * centroid;
+        rigid_body.inertia = rigid_body.mass * compute_inertia(&vertices) / area.abs();
+
+        Ok(Self {
+            material,
+            rigid_body,
+            vertices,
+        })
+    }
+
+    pub fn local_vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    pub fn vertices(&self) -> Vec<Vec2> {
+        let rotation = Mat22::rotation(self.rigid_body.angle);
+        self.vertices
+            .iter()
+            .map(|&vertex| self.rigid_body.position + rotation * vertex)
+            .collect()
+    }
+
+    pub fn area(&self) -> Float {
+        compute_area_and_centroid(&self.vertices).0
+    }
+
+    fn bound(&self, select: impl Fn(Vec2) -> Float, fold: fn(Float, Float) -> Float) -> Float {
+        self.vertices()
+            .into_iter()
+            .map(select)
+            .reduce(fold)
+            .unwrap()
+    }
+}
+
+// 以第一个顶点为参考点划分三角形扇，返回有向面积（逆时针为正）与质心。
+fn compute_area_and_centroid(vertices: &[Vec2]) -> (Float, Vec2) {
+    let origin = vertices[0];
+    let mut area = 0.0;
+    let mut weighted_center = Vec2::new(0.0, 0.0);
+    for i in 1..vertices.len() - 1 {
+        let e1 = vertices[i] - origin;
+        let e2 = vertices[i + 1] - origin;
+        let triangle_area = e1.cross(e2) / 2.0;
+        area += triangle_area;
+        weighted_center += (e1 + e2) * (triangle_area / 3.0);
+    }
+
+    (area, origin + weighted_center / area)
+}
+
+// 单位密度下绕原点的转动惯量，顶点须已平移至以质心为原点。
+fn compute_inertia(vertices: &[Vec2]) -> Float {
+    let mut inertia = 0.0;
+    for i in 0..vertices.len() {
+        let e1 = vertices[i];
+        let e2 = vertices[(i + 1) % vertices.len()];
+        let integral_x2 = e1.x * e1.x + e2.x * e1.x + e2.x * e2.x;
+        let integral_y2 = e1.y * e1.y + e2.y * e1.y + e2.y * e2.y;
+        inertia += e1.cross(e2) / 12.0 * (integral_x2 + integral_y2);
+    }
+
+    inertia
+}
+
+impl Bounded for Polygon {
+    fn bound_left(&self) -> Float {
+        self.bound(|vertex| vertex.x, Float::min)
+    }
+
+    fn bound_top(&self) -> Float {
+        self.bound(|vertex| vertex.y, Float::max)
+    }
+
+    fn bound_right(&self) -> Float {
+        self.bound(|vertex| vertex.x, Float::max)
+    }
+
+    fn bound_bottom(&self) -> Float {
+        self.bound(|vertex| vertex.y, Float::min)
+    }
+}
+
+impl Collider for Polygon {
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig) {
+        other.collide_with_polygon(self, config);
+    }
+
+    fn collide_with_wall(&mut self, body: &Wall, config: &SolverConfig) {
+        collide_wall_and_polygon(body, self, config);
+    }
+
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig) {
+        collide_circle_and_polygon(body, self, config);
+    }
+
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig) {
+        collide_rectangle_and_polygon(body, self, config);
+    }
+
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig) {
+        collide_polygon_and_polygon(self, body, config);
+    }
+
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig) {
+        collide_capsule_and_polygon(body, self, config);
+    }
+
+    fn collide_with_segment(&mut self, body: &mut Segment, config: &SolverConfig) {
+        collide_segment_and_polygon(body, self, config);
+    }
+
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo> {
+        other.detect_with_polygon(self, config)
+    }
+
+    fn detect_with_wall(&self, body: &Wall, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_wall_and_polygon(body, self, config)
+    }
+
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_circle_and_polygon(body, self, config)
+    }
+
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        detect_collision_rectangle_and_polygon(body, self, config)
+    }
+
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_polygon_and_polygon(body, self, config)
+    }
+
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_capsule_and_polygon(body, self, config)
+    }
+
+    fn detect_with_segment(&self, body: &Segment, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_segment_and_polygon(body, self, config)
+    }
+}
+
+// 顶点已移至以质心为原点。
+impl MassLike for Polygon {
+    fn compute_mass(&self, density: Float) -> MassProperties {
+        MassProperties {
+            mass: density * self.area(),
+            center: Vec2::default(),
+            inertia: density * compute_inertia(&self.vertices),
+        }
+    }
+}
+
+impl RigidBodyLike for Polygon {
+    fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        &mut self.rigid_body
+    }
+
+    fn rigid_body(&self) -> &RigidBody {
+        &self.rigid_body
+    }
+}
+
+impl MaterialLike for Polygon {
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
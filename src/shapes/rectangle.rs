@@ -1,8 +1,18 @@
-use super::{Bounded, Circle, Collider, Material, MaterialLike, RigidBody, RigidBodyLike, Wall};
+use super::{
+    Bounded, Capsule, Circle, Collider, MassLike, MassProperties, Material, MaterialLike, Polygon,
+    RigidBody, RigidBodyLike, Segment, Wall,
+};
 use crate::{
-    algebra::Float,
+    algebra::{Float, Mat22, Vec2},
     collide_calculation::{
-        collide_circle_and_rectangle, collide_rectangle_and_rectange, collide_wall_and_rectangle,
+        collide_capsule_and_rectangle, collide_circle_and_rectangle, collide_rectangle_and_polygon,
+        collide_rectangle_and_rectange, collide_segment_and_rectangle, collide_wall_and_rectangle,
+    },
+    contact_solver::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_capsule_and_rectangle, detect_collision_circle_and_rectangle,
+        detect_collision_rectangle_and_polygon, detect_collision_rectangle_and_rectangle,
+        detect_collision_segment_and_rectangle, detect_collision_wall_and_rectangle, CollisionInfo,
     },
 };
 
@@ -11,32 +21,42 @@ pub struct Rectangle {
     rigid_body: RigidBody,
     pub width: Float,
     pub height: Float,
-    pub angle: Float,
 }
 
 impl Rectangle {
-    pub fn new(
-        material: Material,
-        rigid_body: RigidBody,
-        width: Float,
-        height: Float,
-        angle: Float,
-    ) -> Self {
+    // 质量取自 rigid_body，转动惯量由质量与尺寸算出。
+    pub fn new(material: Material, mut rigid_body: RigidBody, width: Float, height: Float) -> Self {
+        rigid_body.inertia = rigid_body.mass * (width * width + height * height) / 12.0;
+
         Self {
             material,
             rigid_body,
             width,
             height,
-            angle,
         }
     }
 
+    pub fn vertices(&self) -> [Vec2; 4] {
+        let rotation = Mat22::rotation(self.rigid_body.angle);
+        let position = self.rigid_body.position;
+        let half_width = self.width / 2.0;
+        let half_height = self.height / 2.0;
+        [
+            position + rotation * Vec2::new(-half_width, -half_height),
+            position + rotation * Vec2::new(half_width, -half_height),
+            position + rotation * Vec2::new(half_width, half_height),
+            position + rotation * Vec2::new(-half_width, half_height),
+        ]
+    }
+
     fn bound_width(&self) -> Float {
-        self.height * self.angle.sin().abs() + self.width * self.angle.cos().abs()
+        let angle = self.rigid_body.angle;
+        self.height * angle.sin().abs() + self.width * angle.cos().abs()
     }
 
     fn bound_height(&self) -> Float {
-        self.height * self.angle.cos().abs() + self.width * self.angle.sin().abs()
+        let angle = self.rigid_body.angle;
+        self.height * angle.cos().abs() + self.width * angle.sin().abs()
     }
 }
 
@@ -59,20 +79,75 @@ impl Bounded for Rectangle {
 }
 
 impl Collider for Rectangle {
-    fn collide_with(&mut self, other: &mut impl Collider) {
-        other.collide_with_rectangle(self);
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig) {
+        other.collide_with_rectangle(self, config);
+    }
+
+    fn collide_with_wall(&mut self, body: &Wall, config: &SolverConfig) {
+        collide_wall_and_rectangle(body, self, config);
+    }
+
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig) {
+        collide_circle_and_rectangle(body, self, config);
+    }
+
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig) {
+        collide_rectangle_and_rectange(self, body, config);
+    }
+
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig) {
+        collide_rectangle_and_polygon(self, body, config);
+    }
+
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig) {
+        collide_capsule_and_rectangle(body, self, config);
     }
 
-    fn collide_with_wall(&mut self, body: &Wall) {
-        collide_wall_and_rectangle(body, self);
+    fn collide_with_segment(&mut self, body: &mut Segment, config: &SolverConfig) {
+        collide_segment_and_rectangle(body, self, config);
     }
 
-    fn collide_with_circle(&mut self, body: &mut Circle) {
-        collide_circle_and_rectangle(body, self);
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo> {
+        other.detect_with_rectangle(self, config)
     }
 
-    fn collide_with_rectangle(&mut self, body: &mut Rectangle) {
-        collide_rectangle_and_rectange(self, body);
+    fn detect_with_wall(&self, body: &Wall, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_wall_and_rectangle(body, self, config)
+    }
+
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_circle_and_rectangle(body, self, config)
+    }
+
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        detect_collision_rectangle_and_rectangle(body, self, config)
+    }
+
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_rectangle_and_polygon(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_capsule_and_rectangle(body, self, config)
+    }
+
+    fn detect_with_segment(&self, body: &Segment, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_segment_and_rectangle(body, self, config)
+    }
+}
+
+impl MassLike for Rectangle {
+    fn compute_mass(&self, density: Float) -> MassProperties {
+        let mass = density * self.width * self.height;
+        MassProperties {
+            mass,
+            center: Vec2::default(),
+            inertia: mass * (self.width * self.width + self.height * self.height) / 12.0,
+        }
     }
 }
 
@@ -0,0 +1,178 @@
+use super::{
+    BodyType, Bounded, Capsule, Circle, Collider, MassLike, MassProperties, Material, MaterialLike,
+    Polygon, Rectangle, RigidBody, RigidBodyLike, Wall,
+};
+use crate::{
+    algebra::{Float, Mat22, Vec2},
+    collide_calculation::{
+        collide_segment_and_capsule, collide_segment_and_circle, collide_segment_and_polygon,
+        collide_segment_and_rectangle,
+    },
+    contact_solver::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_segment_and_capsule, detect_collision_segment_and_circle,
+        detect_collision_segment_and_polygon, detect_collision_segment_and_rectangle,
+        CollisionInfo,
+    },
+};
+
+// 有限长的静态边，顶点为相对于刚体位置的局部坐标。
+// one_sided 为 true 时只与位于法线一侧的物体碰撞，法线指向由 start 到 end 的右侧，
+// 与逆时针多边形的外法线一致。previous 与 next 为相邻边的另一端（幽灵顶点），
+// 用于判断连接处的棱角，使物体越过连接处时不会被卡住；通常由 Chain 设置。
+pub struct Segment {
+    material: Material,
+    rigid_body: RigidBody,
+    pub start: Vec2,
+    pub end: Vec2,
+    pub previous: Option<Vec2>,
+    pub next: Option<Vec2>,
+    pub one_sided: bool,
+}
+
+impl Segment {
+    // 边没有面积，只能作为静态物体。
+    pub fn new(material: Material, mut rigid_body: RigidBody, start: Vec2, end: Vec2) -> Self {
+        rigid_body.body_type = BodyType::Static;
+
+        Self {
+            material,
+            rigid_body,
+            start,
+            end,
+            previous: None,
+            next: None,
+            one_sided: false,
+        }
+    }
+
+    pub fn vertices(&self) -> (Vec2, Vec2) {
+        (self.to_world(self.start), self.to_world(self.end))
+    }
+
+    pub fn ghost_vertices(&self) -> (Option<Vec2>, Option<Vec2>) {
+        (
+            self.previous.map(|vertex| self.to_world(vertex)),
+            self.next.map(|vertex| self.to_world(vertex)),
+        )
+    }
+
+    fn to_world(&self, vertex: Vec2) -> Vec2 {
+        Mat22::rotation(self.rigid_body.angle) * vertex + self.rigid_body.position
+    }
+}
+
+impl Bounded for Segment {
+    fn bound_left(&self) -> Float {
+        let (start, end) = self.vertices();
+        start.x.min(end.x)
+    }
+
+    fn bound_top(&self) -> Float {
+        let (start, end) = self.vertices();
+        start.y.max(end.y)
+    }
+
+    fn bound_right(&self) -> Float {
+        let (start, end) = self.vertices();
+        start.x.max(end.x)
+    }
+
+    fn bound_bottom(&self) -> Float {
+        let (start, end) = self.vertices();
+        start.y.min(end.y)
+    }
+}
+
+// 线段之间、线段与墙体之间都是静态物体，不产生接触。
+impl Collider for Segment {
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig) {
+        other.collide_with_segment(self, config);
+    }
+
+    fn collide_with_wall(&mut self, _body: &Wall, _config: &SolverConfig) {}
+
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig) {
+        collide_segment_and_circle(self, body, config);
+    }
+
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig) {
+        collide_segment_and_rectangle(self, body, config);
+    }
+
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig) {
+        collide_segment_and_polygon(self, body, config);
+    }
+
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig) {
+        collide_segment_and_capsule(self, body, config);
+    }
+
+    fn collide_with_segment(&mut self, _body: &mut Segment, _config: &SolverConfig) {}
+
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo> {
+        other.detect_with_segment(self, config)
+    }
+
+    fn detect_with_wall(&self, _body: &Wall, _config: &SolverConfig) -> Option<CollisionInfo> {
+        None
+    }
+
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_segment_and_circle(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        detect_collision_segment_and_rectangle(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_segment_and_polygon(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_segment_and_capsule(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_segment(
+        &self,
+        _body: &Segment,
+        _config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        None
+    }
+}
+
+// 边没有面积，质量为 0，质心位于中点。
+impl MassLike for Segment {
+    fn compute_mass(&self, _density: Float) -> MassProperties {
+        MassProperties {
+            center: (self.start + self.end) / 2.0,
+            ..Default::default()
+        }
+    }
+}
+
+impl RigidBodyLike for Segment {
+    fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        &mut self.rigid_body
+    }
+
+    fn rigid_body(&self) -> &RigidBody {
+        &self.rigid_body
+    }
+}
+
+impl MaterialLike for Segment {
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
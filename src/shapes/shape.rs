@@ -0,0 +1,161 @@
+use super::{
+    Bounded, Capsule, Circle, Collider, MassLike, MassProperties, Material, MaterialLike, Polygon,
+    Rectangle, RigidBody, RigidBodyLike, Segment, Wall,
+};
+use crate::{algebra::Float, contact_solver::SolverConfig, detection_narrow_phase::CollisionInfo};
+
+pub enum Shape {
+    Circle(Circle),
+    Rectangle(Rectangle),
+    Polygon(Polygon),
+    Capsule(Capsule),
+    Segment(Segment),
+}
+
+macro_rules! dispatch {
+    ($shape:expr, $inner:ident => $body:expr) => {
+        match $shape {
+            Shape::Circle($inner) => $body,
+            Shape::Rectangle($inner) => $body,
+            Shape::Polygon($inner) => $body,
+            Shape::Capsule($inner) => $body,
+            Shape::Segment($inner) => $body,
+        }
+    };
+}
+
+impl From<Circle> for Shape {
+    fn from(circle: Circle) -> Self {
+        Shape::Circle(circle)
+    }
+}
+
+impl From<Rectangle> for Shape {
+    fn from(rect: Rectangle) -> Self {
+        Shape::Rectangle(rect)
+    }
+}
+
+impl From<Polygon> for Shape {
+    fn from(polygon: Polygon) -> Self {
+        Shape::Polygon(polygon)
+    }
+}
+
+impl From<Capsule> for Shape {
+    fn from(capsule: Capsule) -> Self {
+        Shape::Capsule(capsule)
+    }
+}
+
+impl From<Segment> for Shape {
+    fn from(segment: Segment) -> Self {
+        Shape::Segment(segment)
+    }
+}
+
+impl Bounded for Shape {
+    fn bound_left(&self) -> Float {
+        dispatch!(self, shape => shape.bound_left())
+    }
+
+    fn bound_top(&self) -> Float {
+        dispatch!(self, shape => shape.bound_top())
+    }
+
+    fn bound_right(&self) -> Float {
+        dispatch!(self, shape => shape.bound_right())
+    }
+
+    fn bound_bottom(&self) -> Float {
+        dispatch!(self, shape => shape.bound_bottom())
+    }
+}
+
+impl Collider for Shape {
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig) {
+        dispatch!(self, shape => shape.collide_with(other, config))
+    }
+
+    fn collide_with_wall(&mut self, body: &Wall, config: &SolverConfig) {
+        dispatch!(self, shape => shape.collide_with_wall(body, config))
+    }
+
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig) {
+        dispatch!(self, shape => shape.collide_with_circle(body, config))
+    }
+
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig) {
+        dispatch!(self, shape => shape.collide_with_rectangle(body, config))
+    }
+
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig) {
+        dispatch!(self, shape => shape.collide_with_polygon(body, config))
+    }
+
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig) {
+        dispatch!(self, shape => shape.collide_with_capsule(body, config))
+    }
+
+    fn collide_with_segment(&mut self, body: &mut Segment, config: &SolverConfig) {
+        dispatch!(self, shape => shape.collide_with_segment(body, config))
+    }
+
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo> {
+        dispatch!(self, shape => shape.detect_with(other, config))
+    }
+
+    fn detect_with_wall(&self, body: &Wall, config: &SolverConfig) -> Option<CollisionInfo> {
+        dispatch!(self, shape => shape.detect_with_wall(body, config))
+    }
+
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo> {
+        dispatch!(self, shape => shape.detect_with_circle(body, config))
+    }
+
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        dispatch!(self, shape => shape.detect_with_rectangle(body, config))
+    }
+
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo> {
+        dispatch!(self, shape => shape.detect_with_polygon(body, config))
+    }
+
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo> {
+        dispatch!(self, shape => shape.detect_with_capsule(body, config))
+    }
+
+    fn detect_with_segment(&self, body: &Segment, config: &SolverConfig) -> Option<CollisionInfo> {
+        dispatch!(self, shape => shape.detect_with_segment(body, config))
+    }
+}
+
+impl MassLike for Shape {
+    fn compute_mass(&self, density: Float) -> MassProperties {
+        dispatch!(self, shape => shape.compute_mass(density))
+    }
+}
+
+impl RigidBodyLike for Shape {
+    fn rigid_body_mut(&mut self) -> &mut RigidBody {
+        dispatch!(self, shape => shape.rigid_body_mut())
+    }
+
+    fn rigid_body(&self) -> &RigidBody {
+        dispatch!(self, shape => shape.rigid_body())
+    }
+}
+
+impl MaterialLike for Shape {
+    fn material_mut(&mut self) -> &mut Material {
+        dispatch!(self, shape => shape.material_mut())
+    }
+
+    fn material(&self) -> &Material {
+        dispatch!(self, shape => shape.material())
+    }
+}
@@ -1,2 +1,8 @@
+mod test_body;
+mod test_capsule;
 mod test_circle;
+mod test_polygon;
+mod test_rectangle;
+mod test_segment;
+mod test_shape;
 mod test_wall;
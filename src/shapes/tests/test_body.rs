@@ -0,0 +1,243 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{
+    algebra::{equals_float, Vec2},
+    shapes::{
+        Body, Bounded, Circle, CollisionFilter, Fixture, MassProperties, Material, MaterialLike,
+        Rectangle, RigidBody, RigidBodyLike, Shape,
+    },
+};
+
+#[test]
+fn test_body_combines_mass_data() {
+    // 质量 1 与 3 的两个圆分别位于原点与 (4, 0)，质心位于 (3, 0)；转动惯量按平行轴定理合成。
+    let mut body: Body<Shape> = Body::new(RigidBody {
+        position: Vec2::new(10.0, 0.0),
+        ..Default::default()
+    });
+    body.add_fixture(get_fixture(Vec2::new(0.0, 0.0), 1.0));
+    body.add_fixture(get_fixture(Vec2::new(4.0, 0.0), 3.0));
+
+    let rigid_body = body.rigid_body();
+    assert_eq!(rigid_body.mass, 4.0);
+    assert_eq!(body.local_center(), Vec2::new(3.0, 0.0));
+    assert_eq!(rigid_body.position, Vec2::new(13.0, 0.0));
+    assert_eq!(body.origin(), Vec2::new(10.0, 0.0));
+    assert!(equals_float(rigid_body.inertia, 0.5 + 9.0 + 1.5 + 3.0));
+
+    // 夹具的位姿随刚体同步为世界坐标，刚体绕质心转动。
+    body.rigid_body_mut().angle = FRAC_PI_2;
+    body.synchronize_fixtures();
+    let fixtures = body.fixtures();
+    assert!((fixtures[0].shape.rigid_body().position - Vec2::new(13.0, -3.0)).length() < 1e-9);
+    assert!((fixtures[1].shape.rigid_body().position - Vec2::new(13.0, 1.0)).length() < 1e-9);
+    assert!(equals_float(
+        fixtures[1].shape.rigid_body().angle,
+        FRAC_PI_2
+    ));
+    assert!(equals_float(body.bound_top(), 2.0));
+    assert!(equals_float(body.bound_bottom(), -4.0));
+}
+
+#[test]
+fn test_body_keeps_origin_and_velocity_when_mass_changes() {
+    // 转动中的刚体加入夹具后原点不动，质心的速度与其在原刚体上的速度一致。
+    let mut body: Body<Shape> = Body::new(RigidBody {
+        mass: 1.0,
+        inertia: 1.0,
+        position: Vec2::new(0.0, 0.0),
+        angular_velocity: 2.0,
+        ..Default::default()
+    });
+    body.add_fixture(get_fixture(Vec2::new(0.0, 1.0), 1.0));
+    assert_eq!(body.origin(), Vec2::new(0.0, 0.0));
+    assert_eq!(body.rigid_body().position, Vec2::new(0.0, 1.0));
+    assert_eq!(body.rigid_body().velocity, Vec2::new(-2.0, 0.0));
+    assert_eq!(
+        body.fixtures()[0].shape.rigid_body().velocity,
+        Vec2::new(-2.0, 0.0)
+    );
+
+    // 夹具没有质量时保留刚体原有的质量。
+    let mut body: Body<Shape> = Body::new(RigidBody {
+        mass: 5.0,
+        ..Default::default()
+    });
+    body.add_fixture(get_fixture(Vec2::new(1.0, 0.0), 0.0));
+    assert_eq!(body.rigid_body().mass, 5.0);
+    assert_eq!(body.local_center(), Vec2::new(0.0, 0.0));
+}
+
+#[test]
+fn test_fixture_material_and_filter() {
+    let mut fixture: Fixture<Shape> = Fixture::new(
+        Circle::new(
+            Material {
+                restitution: 0.5,
+                ..Default::default()
+            },
+            RigidBody {
+                position: Vec2::new(1.0, 2.0),
+                angle: 0.5,
+                ..Default::default()
+            },
+            1.0,
+        )
+        .into(),
+    );
+    assert_eq!(fixture.offset, Vec2::new(1.0, 2.0));
+    assert_eq!(fixture.angle, 0.5);
+    assert_eq!(fixture.material().restitution, 0.5);
+    fixture.material_mut().restitution = 0.2;
+    assert_eq!(fixture.shape.material().restitution, 0.2);
+
+    // 双方的类别都在对方的掩码中时才会碰撞。
+    let player = CollisionFilter {
+        category_bits: 0x0002,
+        ..Default::default()
+    };
+    let debris = CollisionFilter {
+        category_bits: 0x0004,
+        mask_bits: !0x0002,
+        ..Default::default()
+    };
+    assert!(fixture.filter.should_collide(&player));
+    assert!(fixture.filter.should_collide(&debris));
+    assert!(!player.should_collide(&debris));
+    assert!(!debris.should_collide(&player));
+}
+
+#[test]
+fn test_collision_filter_groups() {
+    // 组号相同且为负时从不碰撞，为正时总是碰撞，不论类别与掩码；组号不同时按类别与掩码判断。
+    let ghost = CollisionFilter {
+        category_bits: 0x0002,
+        mask_bits: 0x0000,
+        group_index: 1,
+    };
+    assert!(ghost.should_collide(&ghost));
+    assert!(!ghost.should_collide(&CollisionFilter::default()));
+
+    let squad = CollisionFilter {
+        group_index: -1,
+        ..Default::default()
+    };
+    assert!(!squad.should_collide(&squad));
+    assert!(squad.should_collide(&CollisionFilter::default()));
+    assert!(squad.should_collide(&CollisionFilter {
+        group_index: -2,
+        ..Default::default()
+    }));
+
+    // 刚体的过滤器作用于其所有夹具。
+    let mut body: Body<Shape> = Body::new(RigidBody::default());
+    body.add_fixture(get_fixture(Vec2::new(0.0, 0.0), 1.0));
+    body.add_fixture(get_fixture(Vec2::new(2.0, 0.0), 1.0));
+    body.set_filter(squad);
+    assert!(body
+        .fixtures()
+        .iter()
+        .all(|fixture| fixture.filter == squad));
+}
+
+#[test]
+fn test_body_mass_from_density_and_override() {
+    // 由密度算出的两个夹具：1x2 的矩形位于原点，2x2 的矩形位于 (3, 0)。
+    let material = Material {
+        density: 1.0,
+        ..Default::default()
+    };
+    let rect = |position: Vec2, width: f64, height: f64| {
+        Fixture::new(Shape::from(Rectangle::new(
+            material,
+            RigidBody {
+                position,
+                ..Default::default()
+            },
+            width,
+            height,
+        )))
+    };
+    let mut body: Body<Shape> = Body::new(RigidBody::default());
+    body.add_fixture(rect(Vec2::new(0.0, 0.0), 1.0, 2.0));
+    body.add_fixture(rect(Vec2::new(3.0, 0.0), 2.0, 2.0));
+    assert!(equals_float(body.rigid_body().mass, 6.0));
+    assert!(equals_float(body.local_center().x, 2.0));
+    let inertia = 2.0 * 5.0 / 12.0 + 2.0 * 4.0 + 4.0 * 8.0 / 12.0 + 4.0 * 1.0;
+    assert!(equals_float(body.rigid_body().inertia, inertia));
+
+    // 显式设置的质量属性保留到下一次 reset_mass_data。
+    body.set_mass_data(MassProperties {
+        mass: 1.0,
+        center: Vec2::new(1.0, 0.0),
+        inertia: 2.0,
+    });
+    assert_eq!(body.rigid_body().mass, 1.0);
+    assert_eq!(body.rigid_body().position, Vec2::new(1.0, 0.0));
+    assert_eq!(body.origin(), Vec2::new(0.0, 0.0));
+
+    // 改变夹具的尺寸后直接由形状重新计算刚体的质量。
+    if let Shape::Rectangle(rect) = &mut body.fixtures_mut()[1].shape {
+        rect.width = 4.0;
+    }
+    body.reset_mass_data();
+    assert!(equals_float(body.rigid_body().mass, 10.0));
+    assert!(equals_float(body.local_center().x, 2.4));
+
+    // 夹具显式设置的质量属性不再由密度算出，质心随夹具的位姿变换。
+    body.fixtures_mut()[0].mass_override = Some(MassProperties {
+        mass: 8.0,
+        center: Vec2::new(0.0, 1.0),
+        inertia: 1.0,
+    });
+    body.reset_mass_data();
+    assert!(equals_float(body.rigid_body().mass, 16.0));
+    assert!(equals_float(body.local_center().x, 1.5));
+    assert!(equals_float(body.local_center().y, 0.5));
+
+    // 单个形状组成的刚体默认由密度算出质量，尺寸改变后 reset_mass_data 重新计算。
+    let mut body = Body::from_shape(Shape::from(Circle::new(
+        Material {
+            density: 2.0,
+            ..Default::default()
+        },
+        RigidBody::default(),
+        1.0,
+    )));
+    assert!(equals_float(body.rigid_body().mass, 2.0 * PI));
+    assert!(body.rigid_body().inverse_mass() > 0.0);
+    if let Shape::Circle(circle) = &mut body.fixtures_mut()[0].shape {
+        circle.radius = 2.0;
+    }
+    body.reset_mass_data();
+    assert!(equals_float(body.rigid_body().mass, 8.0 * PI));
+
+    // 夹具显式设置的质量属性优先于密度。
+    body.fixtures_mut()[0].mass_override = Some(MassProperties {
+        mass: 5.0,
+        center: Vec2::default(),
+        inertia: 2.5,
+    });
+    body.reset_mass_data();
+    assert_eq!(body.rigid_body().mass, 5.0);
+    assert_eq!(body.rigid_body().inertia, 2.5);
+}
+
+// Helper Functions
+
+fn get_fixture(position: Vec2, mass: f64) -> Fixture<Shape> {
+    let mut fixture = Fixture::new(Shape::from(Circle::new(
+        Material::default(),
+        RigidBody {
+            position,
+            ..Default::default()
+        },
+        1.0,
+    )));
+    fixture.mass_override = Some(MassProperties {
+        mass,
+        center: Vec2::default(),
+        inertia: mass / 2.0,
+    });
+    fixture
+}
@@ -0,0 +1,341 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{
+    algebra::{equals_float, Float, Vec2},
+    box2d::SolverConfig,
+    shapes::{
+        Bounded, Capsule, Circle, Collider, Material, Orientation, Polygon, Rectangle, RigidBody,
+        RigidBodyLike, Wall,
+    },
+};
+
+#[test]
+fn test_capsule_mass_properties() {
+    // 半径趋于 0 时退化为细杆，长度为 0 时退化为圆。
+    let rod = get_capsule(Vec2::new(0.0, 0.0), 0.0, 12.0, 1e-9);
+    assert!((rod.rigid_body().inertia - 12.0).abs() < 1e-6);
+    let disc = get_capsule(Vec2::new(0.0, 0.0), 0.0, 0.0, 2.0);
+    assert!(equals_float(disc.rigid_body().inertia, 2.0));
+
+    // 转动惯量介于外接矩形与内含矩形之间。
+    let capsule = get_capsule(Vec2::new(0.0, 0.0), 0.0, 10.0, 2.0);
+    let inner = 1.0 * (10.0 * 10.0 + 4.0 * 4.0) / 12.0;
+    let outer = 1.0 * (14.0 * 14.0 + 4.0 * 4.0) / 12.0;
+    assert!(capsule.rigid_body().inertia > inner);
+    assert!(capsule.rigid_body().inertia < outer);
+    assert!(equals_float(capsule.area(), 40.0 + PI * 4.0));
+
+    // 转动惯量总是由质量与尺寸算出，传入的值不作为显式设置。
+    let heavy = Capsule::new(
+        Material::default(),
+        RigidBody {
+            mass: 2.0,
+            inertia: 3.0,
+            ..Default::default()
+        },
+        10.0,
+        2.0,
+    );
+    assert!(equals_float(
+        heavy.rigid_body().inertia,
+        2.0 * capsule.rigid_body().inertia
+    ));
+}
+
+#[test]
+fn test_capsule_segment_and_bounds() {
+    let capsule = get_capsule(Vec2::new(10.0, 20.0), 0.0, 10.0, 2.0);
+    assert_eq!(
+        capsule.segment(),
+        (Vec2::new(5.0, 20.0), Vec2::new(15.0, 20.0))
+    );
+    assert_eq!(capsule.bound_left(), 3.0);
+    assert_eq!(capsule.bound_right(), 17.0);
+    assert_eq!(capsule.bound_bottom(), 18.0);
+    assert_eq!(capsule.bound_top(), 22.0);
+
+    // 竖直放置时包围盒随之旋转。
+    let capsule = get_capsule(Vec2::new(10.0, 20.0), FRAC_PI_2, 10.0, 2.0);
+    assert!(equals_float(capsule.bound_left(), 8.0));
+    assert!(equals_float(capsule.bound_right(), 12.0));
+    assert!(equals_float(capsule.bound_bottom(), 13.0));
+    assert!(equals_float(capsule.bound_top(), 27.0));
+}
+
+#[test]
+fn test_detect_capsule_and_circle() {
+    let capsule = get_capsule(Vec2::new(0.0, 0.0), 0.0, 10.0, 2.0);
+
+    // 圆位于线段中部上方，法线与线段垂直。
+    let circle = get_circle(Vec2::new(1.0, 4.0), 3.0);
+    let info = capsule
+        .detect_with(&circle, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 1.0));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    assert_eq!(info.contacts.len(), 1);
+    assert!(equals_float(info.contacts[0].point.y, 1.5));
+
+    // 圆位于端点外侧，法线沿端点指向圆心。
+    let circle = get_circle(Vec2::new(9.0, 3.0), 3.5);
+    let info = capsule
+        .detect_with(&circle, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 0.5));
+    assert!(equals_float(info.normal.x, 0.8));
+    assert!(equals_float(info.normal.y, 0.6));
+
+    let circle = get_circle(Vec2::new(10.0, 4.0), 3.5);
+    assert!(capsule
+        .detect_with(&circle, &SolverConfig::default())
+        .is_none());
+}
+
+#[test]
+fn test_detect_degenerate_capsule_and_circle() {
+    // 长度为 0 的胶囊与圆心重合：法线不能为 NaN，与两圆心重合时相同，退化为竖直向上。
+    let mut capsule = get_capsule(Vec2::new(0.0, 0.0), 0.0, 0.0, 2.0);
+    let mut circle = get_circle(Vec2::new(0.0, 0.0), 3.0);
+    let info = circle
+        .detect_with(&capsule, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 5.0));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    assert!(info
+        .contacts
+        .iter()
+        .all(|contact| !contact.point.x.is_nan() && !contact.point.y.is_nan()));
+
+    circle.collide_with(&mut capsule, &SolverConfig::default());
+    for body in [capsule.rigid_body(), circle.rigid_body()] {
+        assert!(body.position.x.is_finite() && body.position.y.is_finite());
+        assert!(body.velocity.x.is_finite() && body.velocity.y.is_finite());
+    }
+}
+
+#[test]
+fn test_detect_capsule_and_capsule() {
+    // 平行叠放时有两个接触点，位于重叠部分的两端。
+    let capsule1 = get_capsule(Vec2::new(0.0, 0.0), 0.0, 10.0, 2.0);
+    let capsule2 = get_capsule(Vec2::new(4.0, 3.5), 0.0, 10.0, 2.0);
+    let info = capsule1
+        .detect_with(&capsule2, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 0.5));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    let mut xs: Vec<Float> = info.contacts.iter().map(|c| c.point.x).collect();
+    xs.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    assert_eq!(xs.len(), 2);
+    assert!(equals_float(xs[0], -1.0));
+    assert!(equals_float(xs[1], 5.0));
+    assert!(info
+        .contacts
+        .iter()
+        .all(|contact| equals_float(contact.point.y, 1.75)));
+
+    // 十字交叉时只有一个接触点。
+    let capsule2 = get_capsule(Vec2::new(0.0, 0.0), FRAC_PI_2, 10.0, 2.0);
+    let info = capsule1
+        .detect_with(&capsule2, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 9.0));
+    assert_eq!(info.contacts.len(), 1);
+
+    // 共线的两个胶囊端点相对，法线沿线段方向。
+    let capsule2 = get_capsule(Vec2::new(13.0, 0.0), 0.0, 10.0, 2.0);
+    let info = capsule1
+        .detect_with(&capsule2, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 1.0));
+    assert!(equals_float(info.normal.x, 1.0));
+    assert_eq!(info.contacts.len(), 1);
+    assert!(equals_float(info.contacts[0].point.x, 6.5));
+
+    // 竖放的胶囊端点靠近另一个胶囊的端点：各分离轴上的距离都小于半径之和，实际上相离。
+    let capsule2 = get_capsule(Vec2::new(8.0, 8.0), FRAC_PI_2, 10.0, 2.0);
+    assert!(capsule1
+        .detect_with(&capsule2, &SolverConfig::default())
+        .is_none());
+}
+
+#[test]
+fn test_detect_capsule_and_rectangle() {
+    // 胶囊平躺在矩形上，两个接触点，法线由矩形指向胶囊。
+    let rect = get_rectangle(Vec2::new(0.0, 0.0), 20.0, 10.0);
+    let capsule = get_capsule(Vec2::new(2.0, 6.5), 0.0, 10.0, 2.0);
+    let info = capsule
+        .detect_with(&rect, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 0.5));
+    assert_eq!(info.normal, Vec2::new(0.0, -1.0));
+    assert_eq!(info.contacts.len(), 2);
+    let flipped = rect
+        .detect_with(&capsule, &SolverConfig::default())
+        .unwrap();
+    assert_eq!(flipped.normal, Vec2::new(0.0, 1.0));
+
+    // 胶囊的端点位于矩形角的外侧，法线由角指向端点。
+    let capsule = get_capsule(Vec2::new(16.0, 6.0), 0.0, 10.0, 2.0);
+    let info = rect
+        .detect_with(&capsule, &SolverConfig::default())
+        .unwrap();
+    assert_eq!(info.contacts.len(), 1);
+    let expected = Vec2::new(1.0, 1.0).normalize();
+    assert!(equals_float(info.normal.x, expected.x));
+    assert!(equals_float(info.normal.y, expected.y));
+    assert!(equals_float(info.penetration, 2.0 - Float::sqrt(2.0)));
+
+    let capsule = get_capsule(Vec2::new(17.0, 7.0), 0.0, 10.0, 2.0);
+    assert!(rect
+        .detect_with(&capsule, &SolverConfig::default())
+        .is_none());
+
+    // 竖直插入矩形内部，沿上表面推出。
+    let capsule = get_capsule(Vec2::new(0.0, 6.0), FRAC_PI_2, 10.0, 2.0);
+    let info = rect
+        .detect_with(&capsule, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 6.0));
+    assert!(equals_float(info.normal.y, 1.0));
+}
+
+#[test]
+fn test_detect_capsule_and_wall() {
+    let wall = Wall::new(Material::default(), 0.0, Orientation::Bottom);
+
+    // 平躺时两端均为接触点。
+    let capsule = get_capsule(Vec2::new(0.0, 1.5), 0.0, 10.0, 2.0);
+    let info = wall
+        .detect_with(&capsule, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 0.5));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    assert_eq!(info.contacts.len(), 2);
+    assert!(equals_float(info.contacts[0].point.y, -0.25));
+
+    // 竖直站立时只有下端接触。
+    let capsule = get_capsule(Vec2::new(0.0, 6.0), FRAC_PI_2, 10.0, 2.0);
+    let info = capsule
+        .detect_with_wall(&wall, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 1.0));
+    assert_eq!(info.contacts.len(), 1);
+    assert!(equals_float(info.contacts[0].point.y, -0.5));
+
+    let capsule = get_capsule(Vec2::new(0.0, 8.0), FRAC_PI_2, 10.0, 2.0);
+    assert!(wall
+        .detect_with(&capsule, &SolverConfig::default())
+        .is_none());
+}
+
+#[test]
+fn test_collide_capsule_momentum_conservation() {
+    // 完全弹性碰撞前后动量守恒，两者相互弹开。
+    let mut capsule = get_capsule(Vec2::new(0.0, 0.0), 0.0, 10.0, 2.0);
+    let mut circle = get_circle(Vec2::new(4.0, 4.5), 3.0);
+    capsule.rigid_body_mut().velocity = Vec2::new(0.0, 2.0);
+    circle.rigid_body_mut().velocity = Vec2::new(1.0, -3.0);
+    let momentum_before = capsule.rigid_body().velocity * capsule.rigid_body().mass
+        + circle.rigid_body().velocity * circle.rigid_body().mass;
+
+    circle.collide_with(&mut capsule, &SolverConfig::default());
+    let momentum_after = capsule.rigid_body().velocity * capsule.rigid_body().mass
+        + circle.rigid_body().velocity * circle.rigid_body().mass;
+    assert!(equals_float(momentum_before.x, momentum_after.x));
+    assert!(equals_float(momentum_before.y, momentum_after.y));
+    assert!(circle.rigid_body().velocity.y > 0.0);
+    assert!(capsule.rigid_body().velocity.y < 0.0);
+    // 偏离中心的碰撞使胶囊转动。
+    assert!(capsule.rigid_body().angular_velocity != 0.0);
+}
+
+#[test]
+fn test_detect_capsule_end_and_polygon_corner() {
+    // 胶囊的右端点靠近正方形的左下角，只有一个顶点接触，法线由端点指向角。
+    let square = Polygon::new(
+        Material::default(),
+        RigidBody::default(),
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ],
+    )
+    .unwrap();
+    let capsule = get_capsule(Vec2::new(-1.3, -0.4), 0.0, 2.0, 0.6);
+    let info = capsule
+        .detect_with(&square, &SolverConfig::default())
+        .unwrap();
+    assert!(equals_float(info.penetration, 0.1));
+    assert_eq!(info.normal, Vec2::new(0.6, 0.8));
+    assert_eq!(info.contacts.len(), 1);
+    assert_eq!(info.contacts[0].point, Vec2::new(0.03, 0.04));
+
+    // 稍稍移动后仍是同一个顶点接触，特征编号不变，以便跨帧匹配。
+    let moved = get_capsule(Vec2::new(-1.31, -0.41), 0.0, 2.0, 0.6);
+    let moved = moved
+        .detect_with(&square, &SolverConfig::default())
+        .unwrap();
+    assert_eq!(moved.contacts.len(), 1);
+    assert_eq!(moved.contacts[0].feature, info.contacts[0].feature);
+
+    // 胶囊的左端点靠近右下角：换了一个顶点，特征编号随之改变。
+    let other = get_capsule(Vec2::new(3.3, -0.4), 0.0, 2.0, 0.6);
+    let other = other
+        .detect_with(&square, &SolverConfig::default())
+        .unwrap();
+    assert_eq!(other.normal, Vec2::new(-0.6, 0.8));
+    assert_eq!(other.contacts.len(), 1);
+    assert_ne!(other.contacts[0].feature, info.contacts[0].feature);
+}
+
+// Helper Functions
+
+fn get_capsule(position: Vec2, angle: Float, length: Float, radius: Float) -> Capsule {
+    Capsule::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            angle,
+            ..Default::default()
+        },
+        length,
+        radius,
+    )
+}
+
+fn get_circle(position: Vec2, radius: Float) -> Circle {
+    Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            ..Default::default()
+        },
+        radius,
+    )
+}
+
+fn get_rectangle(position: Vec2, width: Float, height: Float) -> Rectangle {
+    Rectangle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            ..Default::default()
+        },
+        width,
+        height,
+    )
+}
@@ -2,8 +2,9 @@ use rand::random;
 
 use crate::{
     algebra::{equals_float, Float, Vec2, FLOADT_TOLERANCE},
+    box2d::SolverConfig,
     detection_narrow_phase::detect_collision_circle_and_circle,
-    shapes::{Bounded, Circle, Collider, Material, RigidBody, RigidBodyLike},
+    shapes::{BodyType, Bounded, Circle, Collider, Material, RigidBody, RigidBodyLike},
 };
 
 #[test]
@@ -16,7 +17,7 @@ fn test_collide_circle_speed_exchange() {
     circle1.rigid_body_mut().velocity = v1;
     circle2.rigid_body_mut().velocity = v2;
 
-    circle1.collide_with(&mut circle2);
+    circle1.collide_with(&mut circle2, &SolverConfig::default());
     assert_ne!(circle1.rigid_body().velocity, circle2.rigid_body().velocity);
     assert_eq!(circle1.rigid_body().velocity, v2);
     assert_eq!(circle2.rigid_body().velocity, v1);
@@ -32,7 +33,7 @@ fn test_collide_circle_moving_away() {
     circle1.rigid_body_mut().velocity = v1;
     circle2.rigid_body_mut().velocity = v2;
 
-    circle1.collide_with(&mut circle2);
+    circle1.collide_with(&mut circle2, &SolverConfig::default());
     assert_eq!(circle1.rigid_body().velocity, v1);
     assert_eq!(circle2.rigid_body().velocity, v2);
 }
@@ -49,11 +50,36 @@ fn test_collide_circle_mass_infinity() {
 
     circle2.rigid_body_mut().velocity = v2;
 
-    circle2.collide_with(&mut circle1);
+    circle2.collide_with(&mut circle1, &SolverConfig::default());
     assert_eq!(circle1.rigid_body().velocity, v1);
     assert_eq!(circle2.rigid_body().velocity, Vec2::new(-v2.x, -v2.y));
 }
 
+#[test]
+fn test_collide_circle_zero_mass_and_static() {
+    // 质量为 0 的动态小球视为质量无穷大，碰撞结果中不应出现 NaN。
+    let (mut circle1, mut circle2) = get_two_intersecting_circle();
+    let v2 = Vec2::new(-42.0, 0.0);
+    circle1.rigid_body_mut().mass = 0.0;
+    circle2.rigid_body_mut().velocity = v2;
+
+    circle2.collide_with(&mut circle1, &SolverConfig::default());
+    assert_eq!(circle1.rigid_body().velocity, Vec2::new(0.0, 0.0));
+    assert_eq!(circle1.rigid_body().position, Vec2::new(10.0, 10.0));
+    assert_eq!(circle2.rigid_body().velocity, Vec2::new(-v2.x, -v2.y));
+
+    // 两个静态小球之间不做任何处理，即便设置了速度。
+    let (mut circle1, mut circle2) = get_two_intersecting_circle();
+    circle1.rigid_body_mut().body_type = BodyType::Static;
+    circle2.rigid_body_mut().body_type = BodyType::Static;
+    circle2.rigid_body_mut().velocity = v2;
+
+    circle2.collide_with(&mut circle1, &SolverConfig::default());
+    assert_eq!(circle1.rigid_body().velocity, Vec2::new(0.0, 0.0));
+    assert_eq!(circle2.rigid_body().velocity, v2);
+    assert_eq!(circle2.rigid_body().position, Vec2::new(30.0, 10.0));
+}
+
 #[test]
 fn test_collide_circle_momentum_and_energy_conservation() {
     fn random_float(a: Float, b: Float) -> Float {
@@ -82,11 +108,12 @@ fn test_collide_circle_momentum_and_energy_conservation() {
         let kinetic_energy_before = get_kinetic_energy(circle1.rigid_body(), circle2.rigid_body());
 
         assert_eq!(
-            detect_collision_circle_and_circle(&circle1, &circle2).is_some(),
+            detect_collision_circle_and_circle(&circle1, &circle2, &SolverConfig::default())
+                .is_some(),
             (circle1.radius + circle2.radius).powi(2)
                 > (circle1.rigid_body().position - circle2.rigid_body().position).length_squared()
         );
-        circle1.collide_with(&mut circle2);
+        circle1.collide_with(&mut circle2, &SolverConfig::default());
 
         let momentum_after = get_momentum(circle1.rigid_body(), circle2.rigid_body());
         let kinetic_energy_after = get_kinetic_energy(circle1.rigid_body(), circle2.rigid_body());
@@ -102,22 +129,30 @@ fn get_two_intersecting_circle() -> (Circle, Circle) {
     let zero = Vec2::new(0.0, 0.0);
 
     let circle1 = Circle::new(
-        Material { restitution: 1.0 },
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
         RigidBody {
             mass: 1.0,
             position: Vec2::new(10.0, 10.0),
             velocity: zero,
             acceleration: zero,
+            ..Default::default()
         },
         10.0,
     );
     let circle2 = Circle::new(
-        Material { restitution: 1.0 },
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
         RigidBody {
             mass: 1.0,
             position: Vec2::new(30.0, 10.0),
             velocity: zero,
             acceleration: zero,
+            ..Default::default()
         },
         10.0 + FLOADT_TOLERANCE,
     );
@@ -0,0 +1,247 @@
+use std::f64::consts::PI;
+
+use crate::{
+    algebra::{equals_float, Float, Vec2},
+    box2d::SolverConfig,
+    shapes::{
+        Circle, Collider, MassLike, Material, Orientation, Polygon, PolygonError, Rectangle,
+        RigidBody, RigidBodyLike, Wall,
+    },
+};
+
+#[test]
+fn test_polygon_validation() {
+    let body = RigidBody::default();
+    let material = Material {
+        restitution: 1.0,
+        ..Default::default()
+    };
+    let polygon = |vertices: Vec<Vec2>| Polygon::new(material, body, vertices).err();
+
+    assert_eq!(
+        polygon(vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)]),
+        Some(PolygonError::TooFewVertices)
+    );
+    // 三点共线，面积为 0。
+    assert_eq!(
+        polygon(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0)
+        ]),
+        Some(PolygonError::Degenerate)
+    );
+    // 重复的顶点。
+    assert_eq!(
+        polygon(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0)
+        ]),
+        Some(PolygonError::Degenerate)
+    );
+    // 凹多边形（箭头形）。
+    assert_eq!(
+        polygon(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(1.0, 1.0)
+        ]),
+        Some(PolygonError::NotConvex)
+    );
+
+    // 边长只有 1e-7 的三角形：默认容差下视为重复顶点，传入更小的容差后合法。
+    let tiny = vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1e-7, 0.0),
+        Vec2::new(0.0, 1e-7),
+    ];
+    assert_eq!(polygon(tiny.clone()), Some(PolygonError::Degenerate));
+    assert!(Polygon::with_tolerance(material, body, tiny, 1e-10).is_ok());
+}
+
+#[test]
+fn test_polygon_winding_and_mass() {
+    // 顺时针给出的 2x4 矩形：构造后应变为逆时针，质心移至 (1, 2)，
+    // 质量与转动惯量应与同尺寸的矩形一致。
+    let polygon = Polygon::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 24.0,
+            ..Default::default()
+        },
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 4.0),
+            Vec2::new(2.0, 4.0),
+            Vec2::new(2.0, 0.0),
+        ],
+    )
+    .unwrap();
+
+    assert!(equals_float(polygon.area(), 8.0));
+    assert_eq!(polygon.rigid_body().position, Vec2::new(1.0, 2.0));
+    assert!(equals_float(polygon.rigid_body().mass, 24.0));
+    assert!(equals_float(
+        polygon.rigid_body().inertia,
+        24.0 * (2.0 * 2.0 + 4.0 * 4.0) / 12.0
+    ));
+    assert_eq!(polygon.local_vertices()[0], Vec2::new(1.0, -2.0));
+    assert_eq!(polygon.local_vertices()[1], Vec2::new(1.0, 2.0));
+
+    // 正六边形：转动惯量为 5/12 · m · s²，其中 s 为边长。
+    let hexagon = get_hexagon(Vec2::new(0.0, 0.0), 2.0);
+    let mass = hexagon.rigid_body().mass;
+    assert!(equals_float(mass, 3.0 * 3.0_f64.sqrt() / 2.0 * 4.0));
+    assert!(equals_float(
+        hexagon.rigid_body().inertia,
+        5.0 / 12.0 * mass * 4.0
+    ));
+}
+
+#[test]
+fn test_collide_circle_and_polygon() {
+    // 圆从正右方撞向六边形的边：对心碰撞，质量相等时速度交换。
+    let mut hexagon = get_hexagon(Vec2::new(0.0, 0.0), 2.0);
+    hexagon.rigid_body_mut().mass = 1.0;
+    let mut circle = Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(2.2, 0.0),
+            velocity: Vec2::new(-3.0, 0.0),
+            ..Default::default()
+        },
+        0.6,
+    );
+
+    circle.collide_with(&mut hexagon, &SolverConfig::default());
+    assert_eq!(circle.rigid_body().velocity, Vec2::new(0.0, 0.0));
+    assert_eq!(hexagon.rigid_body().velocity, Vec2::new(-3.0, 0.0));
+    assert!(equals_float(hexagon.rigid_body().angular_velocity, 0.0));
+
+    // 远离后不再碰撞。
+    hexagon.collide_with(&mut circle, &SolverConfig::default());
+    assert_eq!(circle.rigid_body().velocity, Vec2::new(0.0, 0.0));
+    assert_eq!(hexagon.rigid_body().velocity, Vec2::new(-3.0, 0.0));
+}
+
+#[test]
+fn test_collide_polygon_matches_rectangle() {
+    // 与矩形形状完全相同的多边形，碰撞结果应与矩形一致。
+    let material = Material {
+        restitution: 0.5,
+        ..Default::default()
+    };
+    let body = |x: Float, y: Float, vx: Float| RigidBody {
+        mass: 2.0,
+        position: Vec2::new(x, y),
+        angle: 0.3,
+        velocity: Vec2::new(vx, 0.0),
+        ..Default::default()
+    };
+    let square = |x: Float, y: Float, vx: Float| {
+        let mut polygon = Polygon::new(
+            material,
+            body(x, y, vx),
+            vec![
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(-1.0, 1.0),
+            ],
+        )
+        .unwrap();
+        polygon.rigid_body_mut().mass = 2.0;
+        polygon.rigid_body_mut().inertia = 2.0 * 8.0 / 12.0;
+        polygon
+    };
+
+    let mut rect1 = Rectangle::new(material, body(0.0, 0.0, 4.0), 2.0, 2.0);
+    let mut rect2 = Rectangle::new(material, body(1.8, 0.5, -1.0), 2.0, 2.0);
+    let mut polygon1 = square(0.0, 0.0, 4.0);
+    let mut polygon2 = square(1.8, 0.5, -1.0);
+
+    rect1.collide_with(&mut rect2, &SolverConfig::default());
+    polygon1.collide_with(&mut polygon2, &SolverConfig::default());
+    for (rect, polygon) in [(&rect1, &polygon1), (&rect2, &polygon2)] {
+        assert_eq!(rect.rigid_body().velocity, polygon.rigid_body().velocity);
+        assert_eq!(rect.rigid_body().position, polygon.rigid_body().position);
+        assert!(equals_float(
+            rect.rigid_body().angular_velocity,
+            polygon.rigid_body().angular_velocity
+        ));
+    }
+
+    let mut rect2 = Rectangle::new(material, body(1.8, 0.5, -1.0), 2.0, 2.0);
+    let mut polygon1 = square(0.0, 0.0, 4.0);
+    rect2.collide_with(&mut polygon1, &SolverConfig::default());
+    assert!(polygon1.rigid_body().velocity.x < 4.0);
+    assert!(rect2.rigid_body().velocity.x > -1.0);
+}
+
+#[test]
+fn test_collide_polygon_and_wall() {
+    // 六边形落向地面：顶点刺入地面，碰撞后穿模按 baumgarte 比例减少，且竖直速度反向。
+    let mut hexagon = get_hexagon(Vec2::new(0.0, 1.9), 2.0);
+    hexagon.rigid_body_mut().velocity = Vec2::new(0.0, -5.0);
+    let mut wall = Wall::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        0.0,
+        Orientation::Bottom,
+    );
+
+    let config = SolverConfig::default();
+    let depth = |hexagon: &Polygon| {
+        hexagon
+            .vertices()
+            .iter()
+            .map(|vertex| -vertex.y)
+            .fold(Float::NEG_INFINITY, Float::max)
+    };
+    let depth_before = depth(&hexagon);
+    hexagon.collide_with(&mut wall, &config);
+    assert!(equals_float(
+        depth(&hexagon),
+        depth_before * (1.0 - config.baumgarte)
+    ));
+    assert_eq!(hexagon.rigid_body().velocity, Vec2::new(0.0, 5.0));
+}
+
+// Helper Functions
+
+fn get_hexagon(position: Vec2, side: Float) -> Polygon {
+    let vertices = (0..6)
+        .map(|i| {
+            let angle = PI / 3.0 * i as Float + PI / 2.0;
+            Vec2::new(side * angle.cos(), side * angle.sin())
+        })
+        .collect();
+
+    let mut hexagon = Polygon::new(
+        Material {
+            restitution: 1.0,
+            density: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            position,
+            ..Default::default()
+        },
+        vertices,
+    )
+    .unwrap();
+    hexagon.reset_mass_data();
+    hexagon
+}
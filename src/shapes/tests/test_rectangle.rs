@@ -0,0 +1,241 @@
+use std::f64::consts::FRAC_PI_4;
+
+use crate::{
+    algebra::{equals_float, Float, Vec2, FLOADT_TOLERANCE},
+    box2d::SolverConfig,
+    shapes::{Circle, Collider, Material, Rectangle, RigidBody, RigidBodyLike},
+};
+
+#[test]
+fn test_collide_circle_and_rectangle_speed_exchange() {
+    // 质量相等的圆与矩形，对心完全弹性碰撞，末状态：速度交换。
+    let mut circle = get_circle(Vec2::new(0.0, 0.0));
+    let mut rect = get_rectangle(Vec2::new(20.0 - FLOADT_TOLERANCE, 0.0), 0.0);
+    let v1 = Vec2::new(42.0, 0.0);
+    let v2 = Vec2::new(-10.0, 0.0);
+    circle.rigid_body_mut().velocity = v1;
+    rect.rigid_body_mut().velocity = v2;
+
+    circle.collide_with(&mut rect, &SolverConfig::default());
+    assert_eq!(circle.rigid_body().velocity, v2);
+    assert_eq!(rect.rigid_body().velocity, v1);
+}
+
+#[test]
+fn test_collide_circle_and_rectangle_moving_away() {
+    // 正在远离的圆与矩形，不应该被处理碰撞。
+    let mut circle = get_circle(Vec2::new(0.0, 0.0));
+    let mut rect = get_rectangle(Vec2::new(20.0 - FLOADT_TOLERANCE, 0.0), 0.0);
+    let v1 = Vec2::new(-42.0, 3.0);
+    let v2 = Vec2::new(10.0, 0.0);
+    circle.rigid_body_mut().velocity = v1;
+    rect.rigid_body_mut().velocity = v2;
+
+    rect.collide_with(&mut circle, &SolverConfig::default());
+    assert_eq!(circle.rigid_body().velocity, v1);
+    assert_eq!(rect.rigid_body().velocity, v2);
+}
+
+#[test]
+fn test_collide_circle_and_rotated_rectangle() {
+    // 旋转 45° 的正方形，其顶点正对圆心；未旋转时两者相离，旋转后顶点刺入圆内，
+    // 碰撞方向应沿两者中心连线。
+    let distance = 10.0 + 10.0 * 2.0_f64.sqrt() - 1.0;
+    let mut circle = get_circle(Vec2::new(0.0, 0.0));
+    let mut rect = get_rectangle(Vec2::new(distance, 0.0), 0.0);
+    circle.rigid_body_mut().velocity = Vec2::new(1.0, 0.0);
+
+    circle.collide_with(&mut rect, &SolverConfig::default());
+    assert_eq!(circle.rigid_body().velocity, Vec2::new(1.0, 0.0));
+
+    rect.rigid_body_mut().angle = FRAC_PI_4;
+    circle.collide_with(&mut rect, &SolverConfig::default());
+    assert_eq!(circle.rigid_body().velocity, Vec2::new(0.0, 0.0));
+    assert_eq!(rect.rigid_body().velocity, Vec2::new(1.0, 0.0));
+}
+
+#[test]
+fn test_collide_circle_inside_rectangle() {
+    // 圆心落在矩形内部时，应沿最近的边将两者推开。
+    let mut circle = get_circle(Vec2::new(8.0, 3.0));
+    let mut rect = get_rectangle(Vec2::new(0.0, 0.0), 0.0);
+    circle.rigid_body_mut().velocity = Vec2::new(-1.0, 0.0);
+
+    // 接触点偏离矩形中心，矩形在被推开的同时开始转动；完全弹性碰撞前后动量与动能守恒。
+    let momentum_before = get_momentum(circle.rigid_body(), rect.rigid_body());
+    let kinetic_energy_before = get_kinetic_energy(circle.rigid_body(), rect.rigid_body());
+    circle.collide_with(&mut rect, &SolverConfig::default());
+    assert_eq!(
+        get_momentum(circle.rigid_body(), rect.rigid_body()),
+        momentum_before
+    );
+    assert!(equals_float(
+        get_kinetic_energy(circle.rigid_body(), rect.rigid_body()),
+        kinetic_energy_before
+    ));
+    assert!(rect.rigid_body().velocity.x < 0.0);
+    assert!(rect.rigid_body().angular_velocity > 0.0);
+    assert!(circle.rigid_body().position.x > 8.0);
+    assert!(rect.rigid_body().position.x < 0.0);
+    assert_eq!(circle.rigid_body().position.y, 3.0);
+}
+
+#[test]
+fn test_collide_rectangle_speed_exchange() {
+    // 两质量相等的矩形，边对边完全弹性碰撞（两个接触点），末状态：速度交换。
+    let mut rect1 = get_rectangle(Vec2::new(0.0, 0.0), 0.0);
+    let mut rect2 = get_rectangle(Vec2::new(20.0 - FLOADT_TOLERANCE, 0.0), 0.0);
+    let v1 = Vec2::new(42.0, 0.0);
+    let v2 = Vec2::new(-10.0, 0.0);
+    rect1.rigid_body_mut().velocity = v1;
+    rect2.rigid_body_mut().velocity = v2;
+
+    rect1.collide_with(&mut rect2, &SolverConfig::default());
+    assert_eq!(rect1.rigid_body().velocity, v2);
+    assert_eq!(rect2.rigid_body().velocity, v1);
+
+    // 第一次碰撞后两者已在远离，第二次碰撞不应改变速度。
+    rect2.collide_with(&mut rect1, &SolverConfig::default());
+    assert_eq!(rect1.rigid_body().velocity, v2);
+    assert_eq!(rect2.rigid_body().velocity, v1);
+    assert!(equals_float(rect1.rigid_body().angular_velocity, 0.0));
+    assert!(equals_float(rect2.rigid_body().angular_velocity, 0.0));
+}
+
+#[test]
+fn test_collide_rectangle_off_centre() {
+    // 两矩形错开一段距离后边对边碰撞：接触面偏离两者中心，两者均开始转动，
+    // 且动量、角动量与动能守恒。
+    let mut rect1 = get_rectangle(Vec2::new(0.0, 0.0), 0.0);
+    let mut rect2 = get_rectangle(Vec2::new(20.0 - FLOADT_TOLERANCE, 7.0), 0.0);
+    rect1.rigid_body_mut().velocity = Vec2::new(42.0, 0.0);
+    rect2.rigid_body_mut().velocity = Vec2::new(-10.0, 0.0);
+
+    let momentum_before = get_momentum(rect1.rigid_body(), rect2.rigid_body());
+    let angular_momentum_before = get_angular_momentum(rect1.rigid_body(), rect2.rigid_body());
+    let kinetic_energy_before = get_kinetic_energy(rect1.rigid_body(), rect2.rigid_body());
+
+    rect1.collide_with(&mut rect2, &SolverConfig::default());
+    assert_eq!(
+        get_momentum(rect1.rigid_body(), rect2.rigid_body()),
+        momentum_before
+    );
+    assert!(equals_float(
+        get_angular_momentum(rect1.rigid_body(), rect2.rigid_body()),
+        angular_momentum_before
+    ));
+    assert!(equals_float(
+        get_kinetic_energy(rect1.rigid_body(), rect2.rigid_body()),
+        kinetic_energy_before
+    ));
+    assert!(rect1.rigid_body().angular_velocity > 0.0);
+    assert!(rect2.rigid_body().angular_velocity > 0.0);
+}
+
+#[test]
+fn test_collide_rectangle_stack_separation() {
+    // 上方矩形落在下方矩形上，穿模部分应被修正，使两者趋于分离。
+    let mut lower = get_rectangle(Vec2::new(0.0, 0.0), 0.0);
+    let mut upper = get_rectangle(Vec2::new(0.0, 19.0), 0.0);
+    upper.rigid_body_mut().velocity = Vec2::new(0.0, -5.0);
+
+    upper.collide_with(&mut lower, &SolverConfig::default());
+    assert_eq!(upper.rigid_body().velocity, Vec2::new(0.0, 0.0));
+    assert_eq!(lower.rigid_body().velocity, Vec2::new(0.0, -5.0));
+    assert!(upper.rigid_body().position.y > 19.0);
+    assert!(lower.rigid_body().position.y < 0.0);
+}
+
+#[test]
+fn test_collide_small_heavy_rectangles_solves_both_contacts() {
+    // 米制场景中 20cm 见方、10t 重的两个箱子边对边碰撞：两个接触点的有效质量矩阵条件良好，
+    // 但行列式远小于 FLOADT_TOLERANCE，仍应同时求解两个接触点，使两者交换速度且不发生转动。
+    let config = SolverConfig {
+        linear_slop: 0.0005,
+        float_tolerance: 1e-9,
+        ..Default::default()
+    };
+    let get_box = |position: Vec2, velocity: Vec2| {
+        Rectangle::new(
+            Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
+            RigidBody {
+                mass: 10000.0,
+                position,
+                velocity,
+                ..Default::default()
+            },
+            0.2,
+            0.2,
+        )
+    };
+    let v1 = Vec2::new(3.0, 0.0);
+    let v2 = Vec2::new(-1.0, 0.0);
+    let mut box1 = get_box(Vec2::new(0.0, 0.0), v1);
+    let mut box2 = get_box(Vec2::new(0.2 - 1e-7, 0.0), v2);
+
+    box1.collide_with(&mut box2, &config);
+    assert_eq!(box1.rigid_body().velocity, v2);
+    assert_eq!(box2.rigid_body().velocity, v1);
+    assert!(equals_float(box1.rigid_body().angular_velocity, 0.0));
+    assert!(equals_float(box2.rigid_body().angular_velocity, 0.0));
+}
+
+// Helper Functions
+
+fn get_circle(position: Vec2) -> Circle {
+    Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            velocity: Vec2::new(0.0, 0.0),
+            acceleration: Vec2::new(0.0, 0.0),
+            ..Default::default()
+        },
+        10.0,
+    )
+}
+
+fn get_rectangle(position: Vec2, angle: f64) -> Rectangle {
+    Rectangle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            angle,
+            velocity: Vec2::new(0.0, 0.0),
+            acceleration: Vec2::new(0.0, 0.0),
+            ..Default::default()
+        },
+        20.0,
+        20.0,
+    )
+}
+
+fn get_momentum(p1: &RigidBody, p2: &RigidBody) -> Vec2 {
+    p1.velocity * p1.mass + p2.velocity * p2.mass
+}
+
+fn get_angular_momentum(p1: &RigidBody, p2: &RigidBody) -> Float {
+    p1.inertia * p1.angular_velocity
+        + p1.mass * p1.position.cross(p1.velocity)
+        + p2.inertia * p2.angular_velocity
+        + p2.mass * p2.position.cross(p2.velocity)
+}
+
+fn get_kinetic_energy(p1: &RigidBody, p2: &RigidBody) -> Float {
+    (p1.mass * p1.velocity.length_squared()
+        + p1.inertia * p1.angular_velocity * p1.angular_velocity
+        + p2.mass * p2.velocity.length_squared()
+        + p2.inertia * p2.angular_velocity * p2.angular_velocity)
+        / 2.0
+}
@@ -0,0 +1,253 @@
+use crate::{
+    algebra::{equals_float, Vec2},
+    box2d::SolverConfig,
+    shapes::{
+        BodyType, Chain, ChainError, Circle, Collider, Material, Rectangle, RigidBody,
+        RigidBodyLike, Segment,
+    },
+};
+
+#[test]
+fn test_segment_and_circle() {
+    // 双面的线段从两侧都能碰撞，法线由线段指向圆。
+    let segment = get_segment(Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0));
+    assert_eq!(segment.rigid_body().body_type, BodyType::Static);
+
+    let info = segment
+        .detect_with(
+            &get_circle(Vec2::new(1.0, 2.0), 3.0),
+            &SolverConfig::default(),
+        )
+        .unwrap();
+    assert!(equals_float(info.penetration, 1.0));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    assert!(equals_float(info.contacts[0].point.y, -0.5));
+
+    let info = segment
+        .detect_with(
+            &get_circle(Vec2::new(1.0, -2.0), 3.0),
+            &SolverConfig::default(),
+        )
+        .unwrap();
+    assert_eq!(info.normal, Vec2::new(0.0, -1.0));
+
+    // 没有相邻边的端点处，法线由端点指向圆心。
+    let info = segment
+        .detect_with(
+            &get_circle(Vec2::new(7.0, 0.0), 3.0),
+            &SolverConfig::default(),
+        )
+        .unwrap();
+    assert!(equals_float(info.penetration, 1.0));
+    assert!(equals_float(info.normal.x, 1.0));
+
+    // 单面的线段只与法线一侧（由 start 到 end 的右侧）的物体碰撞。
+    let mut segment = get_segment(Vec2::new(5.0, 0.0), Vec2::new(-5.0, 0.0));
+    segment.one_sided = true;
+    assert!(segment
+        .detect_with(
+            &get_circle(Vec2::new(1.0, 2.0), 3.0),
+            &SolverConfig::default()
+        )
+        .is_some());
+    assert!(segment
+        .detect_with(
+            &get_circle(Vec2::new(1.0, -2.0), 3.0),
+            &SolverConfig::default()
+        )
+        .is_none());
+}
+
+#[test]
+fn test_chain_segments_and_ghost_vertices() {
+    let vertices = vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(10.0, 0.0),
+        Vec2::new(20.0, 5.0),
+    ];
+    let chain = Chain::polyline(Material::default(), RigidBody::default(), vertices).unwrap();
+    assert!(!chain.is_closed());
+    let segments = chain.segments();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].previous, None);
+    assert_eq!(segments[0].next, Some(Vec2::new(20.0, 5.0)));
+    assert_eq!(segments[1].previous, Some(Vec2::new(0.0, 0.0)));
+    assert_eq!(segments[1].next, None);
+
+    // 闭合环首尾相连，每条边两端都有幽灵顶点。
+    let mut chain = Chain::closed(
+        Material::default(),
+        RigidBody {
+            position: Vec2::new(100.0, 0.0),
+            ..Default::default()
+        },
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(0.0, 10.0),
+        ],
+    )
+    .unwrap();
+    chain.one_sided = true;
+    let segments = chain.segments();
+    assert_eq!(segments.len(), 3);
+    assert_eq!(segments[0].previous, Some(Vec2::new(0.0, 10.0)));
+    assert_eq!(segments[2].end, Vec2::new(0.0, 0.0));
+    assert_eq!(segments[2].next, Some(Vec2::new(10.0, 0.0)));
+    assert!(segments.iter().all(|segment| segment.one_sided));
+    assert_eq!(
+        segments[1].vertices(),
+        (Vec2::new(110.0, 0.0), Vec2::new(100.0, 10.0))
+    );
+
+    let chain = |vertices: Vec<Vec2>, closed: bool| {
+        if closed {
+            Chain::closed(Material::default(), RigidBody::default(), vertices)
+        } else {
+            Chain::polyline(Material::default(), RigidBody::default(), vertices)
+        }
+        .err()
+    };
+    let (a, b) = (Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0));
+    assert_eq!(chain(vec![a], false), Some(ChainError::TooFewVertices));
+    assert_eq!(chain(vec![a, b], true), Some(ChainError::TooFewVertices));
+    assert_eq!(chain(vec![a, b, b], false), Some(ChainError::Degenerate));
+    assert_eq!(
+        chain(vec![a, b, Vec2::new(1.0, 1.0), a], true),
+        Some(ChainError::Degenerate)
+    );
+    assert_eq!(chain(vec![a, b], false), None);
+
+    // 顶点相距 1e-7：默认容差下视为重复，传入更小的容差后合法。
+    let tiny = vec![a, Vec2::new(1e-7, 0.0), Vec2::new(2e-7, 0.0)];
+    assert_eq!(chain(tiny.clone(), false), Some(ChainError::Degenerate));
+    assert!(Chain::with_tolerance(
+        Material::default(),
+        RigidBody::default(),
+        tiny,
+        false,
+        1e-10
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_box_does_not_catch_on_flat_joint() {
+    // 方块越过两条共线的边的连接处，稍稍陷入地面：
+    // 孤立的线段按分离轴选中方块的侧面，法线水平，方块会被卡住；链条中的线段改为向上推开。
+    let rect = get_rectangle(Vec2::new(9.005, 0.99));
+    let segment = get_segment(Vec2::new(10.0, 0.0), Vec2::new(20.0, 0.0));
+    let info = segment
+        .detect_with(&rect, &SolverConfig::default())
+        .unwrap();
+    assert!(info.normal.x < -0.99);
+
+    let segments = get_flat_chain().segments();
+    let info = segments[1]
+        .detect_with(&rect, &SolverConfig::default())
+        .unwrap();
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    assert!(equals_float(info.penetration, 0.01));
+    let info = segments[0]
+        .detect_with(&rect, &SolverConfig::default())
+        .unwrap();
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+}
+
+#[test]
+fn test_circle_at_joint_touches_one_segment() {
+    // 圆位于连接处正上方，或位于凸角的两条边法线之间时，只由一条边产生接触。
+    let segments = get_flat_chain().segments();
+    let circle = get_circle(Vec2::new(10.0, 1.0), 2.0);
+    let infos: Vec<_> = segments
+        .iter()
+        .filter_map(|segment| segment.detect_with(&circle, &SolverConfig::default()))
+        .collect();
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos[0].normal, Vec2::new(0.0, 1.0));
+
+    let hill = Chain::polyline(
+        Material::default(),
+        RigidBody::default(),
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 5.0),
+            Vec2::new(20.0, 0.0),
+        ],
+    )
+    .unwrap();
+    let circle = get_circle(Vec2::new(10.0, 7.0), 3.0);
+    let infos: Vec<_> = hill
+        .segments()
+        .iter()
+        .filter_map(|segment| segment.detect_with(&circle, &SolverConfig::default()))
+        .collect();
+    assert_eq!(infos.len(), 1);
+    assert!(equals_float(infos[0].normal.y, 1.0));
+
+    // 凹角中的方块与两条边都接触。
+    let valley = Chain::polyline(
+        Material::default(),
+        RigidBody::default(),
+        vec![
+            Vec2::new(-10.0, 10.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 10.0),
+        ],
+    )
+    .unwrap();
+    let rect = get_rectangle(Vec2::new(0.0, 1.9));
+    let normals: Vec<Vec2> = valley
+        .segments()
+        .iter()
+        .filter_map(|segment| segment.detect_with(&rect, &SolverConfig::default()))
+        .map(|info| info.normal)
+        .collect();
+    assert_eq!(normals.len(), 2);
+    assert!(normals[0].x > 0.0 && normals[0].y > 0.0);
+    assert!(normals[1].x < 0.0 && normals[1].y > 0.0);
+}
+
+// Helper Functions
+
+fn get_segment(start: Vec2, end: Vec2) -> Segment {
+    Segment::new(Material::default(), RigidBody::default(), start, end)
+}
+
+fn get_flat_chain() -> Chain {
+    Chain::polyline(
+        Material::default(),
+        RigidBody::default(),
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(20.0, 0.0),
+        ],
+    )
+    .unwrap()
+}
+
+fn get_circle(position: Vec2, radius: f64) -> Circle {
+    Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position,
+            ..Default::default()
+        },
+        radius,
+    )
+}
+
+fn get_rectangle(position: Vec2) -> Rectangle {
+    Rectangle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position,
+            ..Default::default()
+        },
+        2.0,
+        2.0,
+    )
+}
@@ -0,0 +1,284 @@
+use std::f64::consts::PI;
+
+use crate::{
+    algebra::{equals_float, Float, Vec2},
+    box2d::SolverConfig,
+    detection_broad_phase::{detect_by_broad_phase, ShapeIndexPair},
+    shapes::{
+        Bounded, Capsule, Circle, Collider, MassLike, Material, MaterialLike, Orientation, Polygon,
+        Rectangle, RigidBody, RigidBodyLike, Segment, Shape, Wall,
+    },
+};
+
+#[test]
+fn test_shape_dispatch_matches_concrete_shapes() {
+    // 经由 Shape 分派的碰撞，结果应与直接调用具体形状一致，且与调用顺序无关。
+    let mut circle = get_circle(Vec2::new(0.0, 0.0), Vec2::new(5.0, 1.0));
+    let mut rect = get_rectangle(Vec2::new(9.0, 3.0), Vec2::new(-2.0, 0.0));
+    circle.collide_with(&mut rect, &SolverConfig::default());
+    assert!(rect.rigid_body().velocity.x > -2.0);
+
+    let mut shape_circle = Shape::from(get_circle(Vec2::new(0.0, 0.0), Vec2::new(5.0, 1.0)));
+    let mut shape_rect = Shape::from(get_rectangle(Vec2::new(9.0, 3.0), Vec2::new(-2.0, 0.0)));
+    shape_rect.collide_with(&mut shape_circle, &SolverConfig::default());
+
+    assert_eq!(
+        shape_circle.rigid_body().velocity,
+        circle.rigid_body().velocity
+    );
+    assert_eq!(shape_rect.rigid_body().velocity, rect.rigid_body().velocity);
+    assert_eq!(
+        shape_rect.rigid_body().angular_velocity,
+        rect.rigid_body().angular_velocity
+    );
+    assert_eq!(shape_circle.bound_left(), circle.bound_left());
+    assert_eq!(shape_rect.bound_top(), rect.bound_top());
+}
+
+#[test]
+fn test_mixed_shapes_broad_and_narrow_phase() {
+    // 圆、矩形与多边形混合存放：宽检测找出相互重叠的形状对，再经双重分派处理碰撞。
+    let mut shapes: Vec<Shape> = vec![
+        get_circle(Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.0)).into(),
+        get_rectangle(Vec2::new(9.0, 0.0), Vec2::new(-3.0, 0.0)).into(),
+        get_triangle(Vec2::new(9.0, 7.0), Vec2::new(0.0, -3.0)).into(),
+        get_circle(Vec2::new(100.0, 100.0), Vec2::new(1.0, 1.0)).into(),
+    ];
+
+    let pairs = detect_by_broad_phase(&shapes.iter().collect::<Vec<_>>());
+    assert_eq!(pairs.len(), 2);
+    assert!(pairs.contains(&ShapeIndexPair(0, 1)));
+    assert!(pairs.contains(&ShapeIndexPair(1, 2)));
+
+    for ShapeIndexPair(index1, index2) in pairs {
+        let (low, high) = (index1.min(index2), index1.max(index2));
+        let (head, tail) = shapes.split_at_mut(high);
+        head[low].collide_with(&mut tail[0], &SolverConfig::default());
+    }
+
+    assert!(shapes[0].rigid_body().velocity.x < 0.0);
+    assert!(shapes[2].rigid_body().velocity.y > -3.0);
+    assert_eq!(shapes[3].rigid_body().velocity, Vec2::new(1.0, 1.0));
+
+    // 各类形状均能与墙体碰撞，穿模按 baumgarte 比例减少。
+    let wall = Wall::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        0.5,
+        Orientation::Bottom,
+    );
+    let config = SolverConfig::default();
+    for shape in shapes.iter_mut().take(3) {
+        let penetration = 0.5 - shape.bound_bottom();
+        shape.collide_with_wall(&wall, &config);
+        let remaining = (penetration * (1.0 - config.baumgarte)).max(config.linear_slop);
+        assert!(0.5 - shape.bound_bottom() <= remaining + 1e-6);
+    }
+}
+
+#[test]
+fn test_detect_with_is_symmetric() {
+    // 仅检测碰撞而不改变速度；交换检测顺序后法线反向，穿透深度不变。
+    let shapes: Vec<Shape> = vec![
+        get_circle(Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.0)).into(),
+        get_rectangle(Vec2::new(9.0, 0.0), Vec2::new(-3.0, 0.0)).into(),
+        get_triangle(Vec2::new(9.0, 7.0), Vec2::new(0.0, -3.0)).into(),
+        get_triangle(Vec2::new(3.0, 5.0), Vec2::new(0.0, 0.0)).into(),
+        get_capsule(Vec2::new(3.0, -6.0), Vec2::new(0.0, 0.0)).into(),
+    ];
+
+    let mut count = 0;
+    for (i, shape1) in shapes.iter().enumerate() {
+        for shape2 in shapes.iter().skip(i + 1) {
+            let (info, flipped) = match (
+                shape1.detect_with(shape2, &SolverConfig::default()),
+                shape2.detect_with(shape1, &SolverConfig::default()),
+            ) {
+                (Some(info), Some(flipped)) => (info, flipped),
+                (None, None) => continue,
+                _ => panic!("detection depends on the order of the shapes"),
+            };
+            assert_eq!(info.normal, -flipped.normal);
+            assert!(equals_float(info.penetration, flipped.penetration));
+
+            // 法线由 shape1 指向 shape2。
+            let direction = shape2.rigid_body().position - shape1.rigid_body().position;
+            assert!(info.normal * direction > 0.0);
+            count += 1;
+        }
+    }
+    assert!(count >= 5);
+    assert_eq!(shapes[0].rigid_body().velocity, Vec2::new(3.0, 0.0));
+
+    // 与墙体检测时，法线由墙体指向形状。
+    let wall = Wall::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        0.5,
+        Orientation::Bottom,
+    );
+    for shape in shapes.iter().take(2) {
+        let info = shape
+            .detect_with_wall(&wall, &SolverConfig::default())
+            .unwrap();
+        assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+        assert_eq!(
+            wall.detect_with(shape, &SolverConfig::default())
+                .unwrap()
+                .normal,
+            info.normal
+        );
+    }
+}
+
+#[test]
+fn test_mass_properties_from_density() {
+    // 由密度与面积算出质量，转动惯量与其几何形状一致；构造时给定的质量不受密度影响。
+    let material = Material {
+        density: 2.0,
+        ..Default::default()
+    };
+    let circle = Circle::new(material, RigidBody::default(), 3.0);
+    assert_eq!(circle.rigid_body().mass, 0.0);
+    let properties = circle.mass_properties();
+    assert!(equals_float(properties.mass, 2.0 * PI * 9.0));
+    assert!(equals_float(
+        properties.inertia,
+        properties.mass * 9.0 / 2.0
+    ));
+
+    let rect = Rectangle::new(material, RigidBody::default(), 2.0, 4.0).mass_properties();
+    assert!(equals_float(rect.mass, 16.0));
+    assert!(equals_float(rect.inertia, 16.0 * 20.0 / 12.0));
+
+    // 同尺寸的多边形与矩形质量属性一致。
+    let square = Polygon::new(
+        material,
+        RigidBody::default(),
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 4.0),
+            Vec2::new(0.0, 4.0),
+        ],
+    )
+    .unwrap();
+    let properties = Shape::from(square).mass_properties();
+    assert!(equals_float(properties.mass, 16.0));
+    assert!(equals_float(properties.inertia, 16.0 * 20.0 / 12.0));
+    assert_eq!(properties.center, Vec2::new(0.0, 0.0));
+
+    let capsule = Capsule::new(material, RigidBody::default(), 10.0, 2.0);
+    assert!(equals_float(
+        capsule.mass_properties().mass,
+        2.0 * capsule.area()
+    ));
+
+    // 线段没有面积，质心位于中点。
+    let segment = Segment::new(
+        material,
+        RigidBody::default(),
+        Vec2::new(0.0, 0.0),
+        Vec2::new(4.0, 2.0),
+    );
+    assert_eq!(segment.mass_properties().mass, 0.0);
+    assert_eq!(segment.mass_properties().center, Vec2::new(2.0, 1.0));
+}
+
+#[test]
+fn test_explicit_mass_and_reset_after_resizing() {
+    // 构造时给定的质量不被密度覆盖，转动惯量按形状算出。
+    let material = Material {
+        density: 1.0,
+        ..Default::default()
+    };
+    let mut circle = Circle::new(
+        material,
+        RigidBody {
+            mass: 5.0,
+            ..Default::default()
+        },
+        2.0,
+    );
+    assert_eq!(circle.rigid_body().mass, 5.0);
+    assert_eq!(circle.rigid_body().inertia, 10.0);
+
+    // 改变尺寸后质量不会自动更新，reset_mass_data 按密度重新计算。
+    circle.radius = 4.0;
+    assert_eq!(circle.rigid_body().mass, 5.0);
+    circle.reset_mass_data();
+    assert!(equals_float(circle.rigid_body().mass, PI * 16.0));
+    assert!(equals_float(circle.rigid_body().inertia, PI * 16.0 * 8.0));
+
+    let mut shape = Shape::from(Rectangle::new(material, RigidBody::default(), 1.0, 1.0));
+    shape.material_mut().density = 3.0;
+    shape.reset_mass_data();
+    assert!(equals_float(shape.rigid_body().mass, 3.0));
+}
+
+// Helper Functions
+
+fn get_body(position: Vec2, velocity: Vec2) -> RigidBody {
+    RigidBody {
+        mass: 1.0,
+        position,
+        velocity,
+        ..Default::default()
+    }
+}
+
+fn get_circle(position: Vec2, velocity: Vec2) -> Circle {
+    Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        get_body(position, velocity),
+        5.0,
+    )
+}
+
+fn get_rectangle(position: Vec2, velocity: Vec2) -> Rectangle {
+    Rectangle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        get_body(position, velocity),
+        10.0,
+        10.0,
+    )
+}
+
+fn get_triangle(position: Vec2, velocity: Vec2) -> Polygon {
+    let side: Float = 8.0;
+    Polygon::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        get_body(position, velocity),
+        vec![
+            Vec2::new(-side / 2.0, -side / 3.0),
+            Vec2::new(side / 2.0, -side / 3.0),
+            Vec2::new(0.0, side * 2.0 / 3.0),
+        ],
+    )
+    .unwrap()
+}
+
+fn get_capsule(position: Vec2, velocity: Vec2) -> Capsule {
+    Capsule::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        get_body(position, velocity),
+        8.0,
+        2.0,
+    )
+}
@@ -1,8 +1,11 @@
+use std::f64::consts::FRAC_PI_6;
+
 use rand::random;
 
 use crate::{
-    algebra::{Float, Vec2, FLOADT_TOLERANCE},
-    shapes::{Circle, Collider, Material, Orientation, RigidBody, RigidBodyLike, Wall},
+    algebra::{equals_float, Float, Vec2, FLOADT_TOLERANCE},
+    box2d::SolverConfig,
+    shapes::{Circle, Collider, Material, Orientation, Rectangle, RigidBody, RigidBodyLike, Wall},
 };
 
 #[test]
@@ -11,12 +14,16 @@ fn test_collide_circle_and_wall() {
     // 下面测试的墙体刚好等于该矩形框，这样，该圆将与四个朝向的墙体均发生碰撞，方便测试。
     fn get_circle() -> Circle {
         Circle::new(
-            Material { restitution: 1.0 },
+            Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
             RigidBody {
                 mass: 1.0,
                 position: Vec2::new(10.0, 10.0),
                 velocity: Vec2::new(0.0, 0.0),
                 acceleration: Vec2::new(0.0, 0.0),
+                ..Default::default()
             },
             10.0 + FLOADT_TOLERANCE,
         )
@@ -30,22 +37,32 @@ fn test_collide_circle_and_wall() {
         let mut circle = get_circle();
         let v = Vec2::new(random_float(-100.0, 100.0), random_float(-100.0, 100.0));
         circle.rigid_body_mut().velocity = v;
-        let mut wall = Wall::new(Material { restitution: 1.0 }, wall_bound, wall_orientation);
+        let mut wall = Wall::new(
+            Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
+            wall_bound,
+            wall_orientation,
+        );
 
-        // 第一次碰撞：与朝向平行方向上的速度应等大反向，而垂直方向上的速度则不变。
-        circle.collide_with(&mut wall);
+        // 第一次碰撞：若圆正朝墙体运动，与朝向平行方向上的速度应等大反向，而垂直方向上的速度则不变；
+        // 若圆正远离墙体，则速度保持不变。
+        circle.collide_with(&mut wall, &SolverConfig::default());
+        let approaching = v * wall.normal() < 0.0;
         match wall_orientation {
-            Orientation::Left | Orientation::Right => {
+            Orientation::Left | Orientation::Right if approaching => {
                 assert_eq!(circle.rigid_body().velocity, Vec2::new(-v.x, v.y))
             }
-            Orientation::Top | Orientation::Bottom => {
+            Orientation::Top | Orientation::Bottom if approaching => {
                 assert_eq!(circle.rigid_body().velocity, Vec2::new(v.x, -v.y))
             }
+            _ => assert_eq!(circle.rigid_body().velocity, v),
         }
 
         // 第二次碰撞：第一次碰撞应当完全解决碰撞冲突，故第二次碰撞前后速度保持不变。
         let v_before = circle.rigid_body().velocity;
-        circle.collide_with(&mut wall);
+        circle.collide_with(&mut wall, &SolverConfig::default());
         assert_eq!(circle.rigid_body().velocity, v_before);
     }
 
@@ -56,3 +73,215 @@ fn test_collide_circle_and_wall() {
         test_circle_and_wall(0.0, Orientation::Bottom);
     }
 }
+
+#[test]
+fn test_collide_rectangle_and_wall() {
+    // 创建一个中心位于 (10, 10)、旋转 30° 的正方形，其外接框略大于矩形框 [(0, 0), (20, 20)]，
+    // 这样，该矩形将与四个朝向的墙体均发生碰撞。
+    fn get_rectangle() -> Rectangle {
+        Rectangle::new(
+            Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
+            RigidBody {
+                mass: 1.0,
+                position: Vec2::new(10.0, 10.0),
+                angle: FRAC_PI_6,
+                velocity: Vec2::new(0.0, 0.0),
+                acceleration: Vec2::new(0.0, 0.0),
+                ..Default::default()
+            },
+            15.0,
+            15.0,
+        )
+    }
+
+    fn random_float(a: Float, b: Float) -> Float {
+        (b - a) * random::<Float>() + a
+    }
+
+    fn test_rectangle_and_wall(wall_bound: Float, wall_orientation: Orientation) {
+        let mut rect = get_rectangle();
+        let v = Vec2::new(random_float(-100.0, 100.0), random_float(-100.0, 100.0));
+        rect.rigid_body_mut().velocity = v;
+        let mut wall = Wall::new(
+            Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
+            wall_bound,
+            wall_orientation,
+        );
+
+        // 碰撞后：矩形穿入墙体的深度按 baumgarte 比例减少；墙体无摩擦，切向速度不变；
+        // 顶点撞墙会使矩形转动，但完全弹性碰撞前后动能守恒，且接触点不再朝墙体运动。
+        let vertices = rect.vertices();
+        let deepest = (0..vertices.len())
+            .max_by(|&i, &j| {
+                wall.depth(vertices[i])
+                    .partial_cmp(&wall.depth(vertices[j]))
+                    .unwrap()
+            })
+            .unwrap();
+        let config = SolverConfig::default();
+        let depth = |rect: &Rectangle, wall: &Wall| {
+            rect.vertices()
+                .iter()
+                .map(|&vertex| wall.depth(vertex))
+                .fold(Float::NEG_INFINITY, Float::max)
+        };
+        let depth_before = depth(&rect, &wall);
+        let kinetic_energy_before = get_kinetic_energy(rect.rigid_body());
+        rect.collide_with(&mut wall, &config);
+        let remaining = (depth_before * (1.0 - config.baumgarte)).max(config.linear_slop);
+        assert!(depth(&rect, &wall) < remaining + FLOADT_TOLERANCE);
+
+        let normal = wall.normal();
+        let tangent = Vec2::new(-normal.y, normal.x);
+        assert!(equals_float(
+            rect.rigid_body().velocity * tangent,
+            v * tangent
+        ));
+        assert!(equals_float(
+            get_kinetic_energy(rect.rigid_body()),
+            kinetic_energy_before
+        ));
+        if v * normal < 0.0 {
+            assert!(rect.rigid_body().angular_velocity != 0.0);
+        }
+        let contact = rect.vertices()[deepest];
+        assert!(rect.rigid_body().velocity_at(contact) * normal > -FLOADT_TOLERANCE);
+
+        // 第二次碰撞：第一次碰撞应当完全解决碰撞冲突，故第二次碰撞前后速度保持不变。
+        let v_before = rect.rigid_body().velocity;
+        rect.collide_with(&mut wall, &SolverConfig::default());
+        assert_eq!(rect.rigid_body().velocity, v_before);
+    }
+
+    for _ in 0..10000 {
+        test_rectangle_and_wall(0.0, Orientation::Left);
+        test_rectangle_and_wall(20.0, Orientation::Top);
+        test_rectangle_and_wall(20.0, Orientation::Right);
+        test_rectangle_and_wall(0.0, Orientation::Bottom);
+    }
+}
+
+#[test]
+fn test_collide_circle_and_sloped_wall() {
+    // 斜坡 y = x 的上方为内侧，圆心落在斜坡上、竖直下落的圆与其碰撞：
+    // 圆被沿法线推出至与斜坡相切，法向速度反向，切向速度不变。
+    let wall = Wall::half_plane(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        Vec2::new(-1.0, 1.0),
+        0.0,
+    );
+    assert_eq!(wall.normal(), Vec2::new(-1.0, 1.0).normalize());
+    assert!(equals_float(wall.offset(), 0.0));
+
+    let mut circle = Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(5.0, 5.0),
+            velocity: Vec2::new(0.0, -10.0),
+            ..Default::default()
+        },
+        1.0,
+    );
+    let config = SolverConfig::default();
+    circle.collide_with_wall(&wall, &config);
+
+    // 圆心位于墙面上，穿模深度 1 按 baumgarte 比例修正。
+    let normal = wall.normal();
+    let tangent = Vec2::new(-normal.y, normal.x);
+    assert!(equals_float(
+        -wall.depth(circle.rigid_body().position),
+        config.baumgarte
+    ));
+    assert_eq!(circle.rigid_body().velocity, Vec2::new(-10.0, 0.0));
+    assert!(equals_float(
+        circle.rigid_body().velocity * tangent,
+        Vec2::new(0.0, -10.0) * tangent
+    ));
+
+    // 经过某点的半平面与指定 offset 的半平面等价。
+    let through = Wall::through_point(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        Vec2::new(0.0, 2.0),
+        Vec2::new(3.0, 4.0),
+    );
+    assert_eq!(through.normal(), Vec2::new(0.0, 1.0));
+    assert!(equals_float(through.offset(), 4.0));
+}
+
+#[test]
+#[should_panic(expected = "Not Supported")]
+fn test_half_plane_rejects_zero_normal() {
+    Wall::half_plane(Material::default(), Vec2::new(0.0, 0.0), 1.0);
+}
+
+#[test]
+fn test_wall_friction() {
+    // 圆斜向撞击地面：静摩擦足够大时，接触点在碰撞后不再滑动，圆开始滚动；
+    // 静摩擦不足时，接触点滑动，切向冲量为动摩擦系数乘以法向冲量。
+    fn collide(static_friction: Float, dynamic_friction: Float) -> Circle {
+        let material = Material {
+            restitution: 0.0,
+            static_friction,
+            dynamic_friction,
+            ..Default::default()
+        };
+        let wall = Wall::new(material, 0.0, Orientation::Bottom);
+        let mut circle = Circle::new(
+            material,
+            RigidBody {
+                mass: 1.0,
+                position: Vec2::new(0.0, 1.0 - 1e-9),
+                velocity: Vec2::new(3.0, -2.0),
+                ..Default::default()
+            },
+            1.0,
+        );
+        circle.collide_with_wall(&wall, &SolverConfig::default());
+        circle
+    }
+
+    let rolling = collide(1.0, 1.0);
+    let contact = rolling.rigid_body().position - Vec2::new(0.0, 1.0);
+    assert_eq!(
+        rolling.rigid_body().velocity_at(contact),
+        Vec2::new(0.0, 0.0)
+    );
+    assert!(rolling.rigid_body().angular_velocity < 0.0);
+    // 实心圆滚动时 v = (m r² v0) / (m r² + I) = 2 v0 / 3。
+    assert!(equals_float(rolling.rigid_body().velocity.x, 2.0));
+
+    // 法向冲量为 2，动摩擦冲量为 0.2，切向速度减至 2.8。
+    let sliding = collide(0.1, 0.1);
+    assert!(equals_float(sliding.rigid_body().velocity.x, 2.8));
+    assert!(equals_float(sliding.rigid_body().velocity.y, 0.0));
+
+    let frictionless = collide(0.0, 0.0);
+    assert_eq!(frictionless.rigid_body().velocity, Vec2::new(3.0, 0.0));
+    assert!(equals_float(
+        frictionless.rigid_body().angular_velocity,
+        0.0
+    ));
+}
+
+// Helper Functions
+
+fn get_kinetic_energy(p: &RigidBody) -> Float {
+    (p.mass * p.velocity.length_squared() + p.inertia * p.angular_velocity * p.angular_velocity)
+        / 2.0
+}
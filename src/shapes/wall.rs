@@ -1,40 +1,132 @@
-use super::{Circle, Collider, Material, MaterialLike, Orientation, Rectangle};
+use super::{
+    Capsule, Circle, Collider, CollisionFilter, Material, MaterialLike, Orientation, Polygon,
+    Rectangle, Segment,
+};
 use crate::{
-    algebra::Float,
-    collide_calculation::{collide_wall_and_circle, collide_wall_and_rectangle},
+    algebra::{Float, Vec2},
+    collide_calculation::{
+        collide_wall_and_capsule, collide_wall_and_circle, collide_wall_and_polygon,
+        collide_wall_and_rectangle,
+    },
+    contact_solver::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_wall_and_capsule, detect_collision_wall_and_circle,
+        detect_collision_wall_and_polygon, detect_collision_wall_and_rectangle, CollisionInfo,
+    },
 };
 
+// 半平面边界：满足 normal * p >= offset 的点位于边界内侧，法线指向内侧。
+// filter 与物体的夹具一样参与碰撞过滤。
 pub struct Wall {
     material: Material,
-    pub bound: Float,
-    pub orientation: Orientation,
+    normal: Vec2,
+    offset: Float,
+    pub filter: CollisionFilter,
 }
 
 impl Wall {
     pub fn new(material: Material, bound: Float, orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::Left => Self::half_plane(material, Vec2::new(1.0, 0.0), bound),
+            Orientation::Top => Self::half_plane(material, Vec2::new(0.0, -1.0), -bound),
+            Orientation::Right => Self::half_plane(material, Vec2::new(-1.0, 0.0), -bound),
+            Orientation::Bottom => Self::half_plane(material, Vec2::new(0.0, 1.0), bound),
+        }
+    }
+
+    // 法线无需为单位向量，offset 会随之一起缩放；法线为零向量时无法确定边界。
+    pub fn half_plane(material: Material, normal: Vec2, offset: Float) -> Self {
+        let unit_normal = normal
+            .try_normalize()
+            .expect("Not Supported: the normal of a half-plane must not be zero");
         Self {
             material,
-            bound,
-            orientation,
+            normal: unit_normal,
+            offset: offset / normal.length(),
+            filter: CollisionFilter::default(),
         }
     }
+
+    // 经过 point、法线为 normal 的半平面。
+    pub fn through_point(material: Material, normal: Vec2, point: Vec2) -> Self {
+        Self::half_plane(material, normal, normal * point)
+    }
+
+    pub fn normal(&self) -> Vec2 {
+        self.normal
+    }
+
+    pub fn offset(&self) -> Float {
+        self.offset
+    }
+
+    pub fn depth(&self, point: Vec2) -> Float {
+        self.offset - self.normal * point
+    }
 }
 
 impl Collider for Wall {
-    fn collide_with(&mut self, other: &mut impl Collider) {
-        other.collide_with_wall(self);
+    fn collide_with(&mut self, other: &mut impl Collider, config: &SolverConfig) {
+        other.collide_with_wall(self, config);
+    }
+
+    fn collide_with_wall(&mut self, _body: &Wall, _config: &SolverConfig) {
+        panic!("Not Supported")
+    }
+
+    fn collide_with_circle(&mut self, body: &mut Circle, config: &SolverConfig) {
+        collide_wall_and_circle(self, body, config);
+    }
+
+    fn collide_with_rectangle(&mut self, body: &mut Rectangle, config: &SolverConfig) {
+        collide_wall_and_rectangle(self, body, config);
+    }
+
+    fn collide_with_polygon(&mut self, body: &mut Polygon, config: &SolverConfig) {
+        collide_wall_and_polygon(self, body, config);
+    }
+
+    fn collide_with_capsule(&mut self, body: &mut Capsule, config: &SolverConfig) {
+        collide_wall_and_capsule(self, body, config);
+    }
+
+    // 线段是静态物体，与墙体之间不产生接触。
+    fn collide_with_segment(&mut self, _body: &mut Segment, _config: &SolverConfig) {}
+
+    fn detect_with(&self, other: &impl Collider, config: &SolverConfig) -> Option<CollisionInfo> {
+        other.detect_with_wall(self, config)
     }
 
-    fn collide_with_wall(&mut self, _body: &Wall) {
+    fn detect_with_wall(&self, _body: &Wall, _config: &SolverConfig) -> Option<CollisionInfo> {
         panic!("Not Supported")
     }
 
-    fn collide_with_circle(&mut self, body: &mut Circle) {
-        collide_wall_and_circle(self, body);
+    fn detect_with_circle(&self, body: &Circle, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_wall_and_circle(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_rectangle(
+        &self,
+        body: &Rectangle,
+        config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        detect_collision_wall_and_rectangle(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_polygon(&self, body: &Polygon, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_wall_and_polygon(self, body, config).map(CollisionInfo::flip)
+    }
+
+    fn detect_with_capsule(&self, body: &Capsule, config: &SolverConfig) -> Option<CollisionInfo> {
+        detect_collision_wall_and_capsule(self, body, config).map(CollisionInfo::flip)
     }
 
-    fn collide_with_rectangle(&mut self, body: &mut Rectangle) {
-        collide_wall_and_rectangle(self, body);
+    fn detect_with_segment(
+        &self,
+        _body: &Segment,
+        _config: &SolverConfig,
+    ) -> Option<CollisionInfo> {
+        None
     }
 }
 
@@ -1,3 +1,6 @@
 mod test_algebra;
+mod test_box2d;
 mod test_detection_broad_phase;
+mod test_detection_continuous;
 mod test_detection_narrow_phase;
+mod test_integrator;
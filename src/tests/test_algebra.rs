@@ -58,4 +58,109 @@ mod test_vec2 {
         assert_eq!(one.length_squared(), 2.0);
         assert_eq!(vector1.length_squared(), 2.0 * 2.0 + 42.0 * 42.0);
     }
+
+    #[test]
+    fn test_length_and_normalize() {
+        let zero = Vec2::new(0.0, 0.0);
+        let vector1 = Vec2::new(3.0, -4.0);
+
+        assert_eq!(vector1.length(), 5.0);
+        assert_eq!(vector1.distance(zero), 5.0);
+        assert_eq!(vector1.normalize(), Vec2::new(0.6, -0.8));
+        assert_eq!(vector1.try_normalize(), Some(Vec2::new(0.6, -0.8)));
+        assert_eq!(zero.try_normalize(), None);
+        assert_eq!(Vec2::new(3e-7, -4e-7).try_normalize(), None);
+        assert_eq!(
+            Vec2::new(3e-7, -4e-7).try_normalize_with_tolerance(1e-9),
+            Some(Vec2::new(0.6, -0.8))
+        );
+        assert!(equals_float(vector1.normalize().length(), 1.0));
+    }
+
+    #[test]
+    fn test_cross_and_perp() {
+        let x = Vec2::new(1.0, 0.0);
+        let y = Vec2::new(0.0, 1.0);
+        let vector1 = Vec2::new(2.0, 3.0);
+
+        assert_eq!(x.cross(y), 1.0);
+        assert_eq!(y.cross(x), -1.0);
+        assert_eq!(vector1.cross(vector1), 0.0);
+        assert_eq!(x.perp(), y);
+        assert_eq!(y.perp(), -x);
+        assert_eq!(Vec2::scalar_cross(2.0, vector1), vector1.perp() * 2.0);
+        assert!(equals_float(vector1.dot(vector1.perp()), 0.0));
+        assert!(equals_float(vector1.dot(vector1), vector1 * vector1));
+    }
+
+    #[test]
+    fn test_assign_operators_and_lerp() {
+        let mut vector1 = Vec2::new(2.0, 42.0);
+        vector1 += Vec2::new(1.0, 1.0);
+        assert_eq!(vector1, Vec2::new(3.0, 43.0));
+        vector1 -= Vec2::new(3.0, 3.0);
+        assert_eq!(vector1, Vec2::new(0.0, 40.0));
+        vector1 *= 0.5;
+        assert_eq!(vector1, Vec2::new(0.0, 20.0));
+        vector1 /= 4.0;
+        assert_eq!(vector1, Vec2::new(0.0, 5.0));
+        assert_eq!(2.0 * vector1, Vec2::new(0.0, 10.0));
+        assert_eq!(-vector1, Vec2::new(0.0, -5.0));
+
+        let a = Vec2::new(0.0, 0.0);
+        let b = Vec2::new(10.0, -20.0);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.25), Vec2::new(2.5, -5.0));
+    }
+}
+
+mod test_mat22 {
+    use std::f64::consts::FRAC_PI_2;
+
+    use crate::algebra::{equals_float, Mat22, Vec2, FLOADT_TOLERANCE};
+
+    #[test]
+    fn test_identity_and_rotation() {
+        let vector1 = Vec2::new(2.0, 3.0);
+
+        assert_eq!(Mat22::identity() * vector1, vector1);
+        assert_eq!(Mat22::rotation(FRAC_PI_2) * vector1, vector1.perp());
+        let rotation = Mat22::rotation(0.7);
+        assert_eq!(
+            rotation.transpose() * (rotation * vector1),
+            Mat22::identity() * vector1
+        );
+    }
+
+    #[test]
+    fn test_determinant_inverse_and_solve() {
+        let matrix = Mat22::new(4.0, 7.0, 2.0, 6.0);
+        assert!(equals_float(matrix.determinant(), 10.0));
+        assert_eq!(matrix.inverse().unwrap(), Mat22::new(0.6, -0.7, -0.2, 0.4));
+
+        let x = Vec2::new(-1.0, 2.0);
+        assert_eq!(matrix.solve(matrix * x), Some(x));
+
+        let singular = Mat22::new(1.0, 2.0, 2.0, 4.0);
+        assert!(singular.inverse().is_none());
+        assert!(singular.solve(Vec2::new(1.0, 1.0)).is_none());
+
+        // 元素量级很小但条件良好的矩阵，行列式远小于 FLOADT_TOLERANCE，仍可求逆。
+        let small = matrix * 1e-4;
+        assert!(small.determinant().abs() < FLOADT_TOLERANCE);
+        assert!(small.inverse().is_some());
+        assert_eq!(small.solve(small * x), Some(x));
+        assert!((singular * 1e-4).solve_with_tolerance(x, 1e-9).is_none());
+    }
+
+    #[test]
+    fn test_add_and_subtract() {
+        let matrix1 = Mat22::new(1.0, 2.0, 3.0, 4.0);
+        let matrix2 = Mat22::new(4.0, 3.0, 2.0, 1.0);
+
+        assert_eq!(matrix1 + matrix2, Mat22::new(5.0, 5.0, 5.0, 5.0));
+        assert_eq!(matrix1 - matrix1, Mat22::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(matrix1.transpose(), Mat22::new(1.0, 3.0, 2.0, 4.0));
+    }
 }
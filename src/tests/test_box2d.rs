@@ -0,0 +1,1112 @@
+use std::f64::consts::PI;
+
+use crate::{
+    algebra::{equals_float, Float, Vec2},
+    box2d::{SolverConfig, StepConfig, World},
+    detection_broad_phase::{Aabb, DynamicTree, SpatialHash},
+    shapes::{
+        Body, BodyType, Capsule, Chain, Circle, CollisionFilter, Fixture, Material, Orientation,
+        Rectangle, RigidBody, RigidBodyLike, Shape, Wall,
+    },
+};
+
+#[test]
+fn test_insert_get_and_next_frame() {
+    let mut world = World::builder().bounds(0.0, 0.0, 100.0, 100.0).build();
+    assert!(world.is_empty());
+
+    let circle = world.insert(get_circle(Vec2::new(20.0, 50.0), Vec2::new(10.0, 0.0)));
+    let rect = world.insert(get_rectangle(Vec2::new(80.0, 50.0), Vec2::new(0.0, 0.0)));
+    assert_eq!(world.len(), 2);
+
+    world.next_frame(1.0);
+    assert_eq!(
+        world.get(circle).unwrap().rigid_body().position,
+        Vec2::new(30.0, 50.0)
+    );
+    assert_eq!(
+        world.get(rect).unwrap().rigid_body().position,
+        Vec2::new(80.0, 50.0)
+    );
+
+    world.get_mut(rect).unwrap().rigid_body_mut().velocity = Vec2::new(0.0, -5.0);
+    world.next_frame(1.0);
+    assert_eq!(
+        world.get(rect).unwrap().rigid_body().position,
+        Vec2::new(80.0, 45.0)
+    );
+
+    let handles: Vec<_> = world.iter().map(|(handle, _)| handle).collect();
+    assert_eq!(handles, vec![circle, rect]);
+}
+
+#[test]
+fn test_remove_keeps_other_handles_valid() {
+    let mut world = World::builder().build();
+    let handles: Vec<_> = (0..5)
+        .map(|i| {
+            world.insert(get_circle(
+                Vec2::new(i as f64 * 100.0, 0.0),
+                Vec2::new(0.0, 0.0),
+            ))
+        })
+        .collect();
+
+    // 删除中间的物体后，其余句柄仍指向原来的物体。
+    assert!(world.remove(handles[1]).is_some());
+    assert!(world.remove(handles[1]).is_none());
+    assert!(world.get(handles[1]).is_none());
+    for (i, &handle) in handles.iter().enumerate().filter(|(i, _)| *i != 1) {
+        assert_eq!(
+            world.get(handle).unwrap().rigid_body().position,
+            Vec2::new(i as f64 * 100.0, 0.0)
+        );
+    }
+
+    // 新插入的物体复用空出的位置，但旧句柄不会指向它。
+    let reused = world.insert(get_circle(Vec2::new(-1.0, -1.0), Vec2::new(0.0, 0.0)));
+    assert_ne!(reused, handles[1]);
+    assert!(world.get(handles[1]).is_none());
+    assert_eq!(
+        world.get(reused).unwrap().rigid_body().position,
+        Vec2::new(-1.0, -1.0)
+    );
+    assert_eq!(world.len(), 5);
+}
+
+#[test]
+fn test_bodies_collide_inside_the_box() {
+    // 两小球相向运动，碰撞后速度交换；小球撞墙后反弹，穿入墙体的深度不超过一帧的位移。
+    let mut world = World::builder().bounds(0.0, 0.0, 100.0, 100.0).build();
+    let left = world.insert(get_circle(Vec2::new(40.0, 50.0), Vec2::new(5.0, 0.0)));
+    let right = world.insert(get_circle(Vec2::new(60.0, 50.0), Vec2::new(-5.0, 0.0)));
+
+    for _ in 0..5 {
+        world.next_frame(0.5);
+    }
+    assert!(world.get(left).unwrap().rigid_body().velocity.x < 0.0);
+    assert!(world.get(right).unwrap().rigid_body().velocity.x > 0.0);
+
+    for _ in 0..100 {
+        world.next_frame(0.5);
+        for (_, shape) in world.iter() {
+            let position = shape.rigid_body().position;
+            assert!(position.x >= 10.0 - 2.5 && position.x <= 90.0 + 2.5);
+        }
+    }
+}
+
+#[test]
+fn test_circle_settles_in_v_shaped_funnel() {
+    // 两个倾斜的半平面组成 V 形漏斗，受重力下落的圆最终停在漏斗底部，与两侧斜面相切。
+    let material = Material {
+        restitution: 0.0,
+        ..Default::default()
+    };
+    let mut world = World::builder()
+        .wall(Wall::half_plane(material, Vec2::new(1.0, 1.0), 0.0))
+        .wall(Wall::half_plane(material, Vec2::new(-1.0, 1.0), 0.0))
+        .build();
+    assert_eq!(world.walls.len(), 2);
+
+    let circle = world.insert(Circle::new(
+        material,
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(3.0, 10.0),
+            acceleration: Vec2::new(0.0, -10.0),
+            ..Default::default()
+        },
+        1.0,
+    ));
+    for _ in 0..2000 {
+        world.next_frame(0.01);
+    }
+
+    let position = world.get(circle).unwrap().rigid_body().position;
+    assert!(position.distance(Vec2::new(0.0, 2.0_f64.sqrt())) < 0.05);
+    for wall in &world.walls {
+        assert!(wall.depth(position) < -1.0 + 0.05);
+    }
+}
+
+#[test]
+fn test_box_slides_to_rest_with_friction() {
+    // 地面上以 5 的初速度滑动的方块，动摩擦系数 0.5、重力加速度 10，
+    // 应在滑行约 v² / (2μg) = 2.5 的距离后停下。
+    let material = Material {
+        restitution: 0.0,
+        static_friction: 0.5,
+        dynamic_friction: 0.5,
+        ..Default::default()
+    };
+    let mut world = World::builder()
+        .wall(Wall::new(material, 0.0, Orientation::Bottom))
+        .build();
+    let rect = world.insert(Rectangle::new(
+        material,
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(0.0, 0.5),
+            velocity: Vec2::new(5.0, 0.0),
+            acceleration: Vec2::new(0.0, -10.0),
+            ..Default::default()
+        },
+        1.0,
+        1.0,
+    ));
+
+    for _ in 0..300 {
+        world.next_frame(0.01);
+    }
+
+    let body = world.get(rect).unwrap().rigid_body();
+    assert!(body.velocity.x.abs() < 1e-3);
+    assert!((body.position.x - 2.5).abs() < 0.1);
+    assert!(body.angle.abs() < 0.01);
+}
+
+#[test]
+fn test_stack_stays_at_rest() {
+    // 三个方块叠放在地面上：求解器同时处理所有接触，叠放的方块既不下沉也不抖动；
+    // 借助热启动，即便每帧只迭代一次，冲量也会逐帧收敛。
+    for velocity_iterations in [1, 10] {
+        let config = SolverConfig {
+            velocity_iterations,
+            ..Default::default()
+        };
+        assert_stack_stays_at_rest(config, 1.0);
+    }
+}
+
+#[test]
+fn test_solver_config_scales_with_units() {
+    // 以像素为单位的场景：尺寸与重力放大 100 倍，容差也随之放大，结果应与以米为单位时一致。
+    let config = SolverConfig {
+        linear_slop: 1.0,
+        float_tolerance: 1e-4,
+        ..Default::default()
+    };
+    assert_stack_stays_at_rest(config, 100.0);
+
+    // 低于恢复速度阈值的碰撞不再反弹：小球落地后静止下来。
+    for (threshold, bounced) in [(0.0, true), (20.0, false)] {
+        let config = SolverConfig {
+            restitution_velocity_threshold: threshold,
+            ..Default::default()
+        };
+        let mut world = World::builder()
+            .bounds(-100.0, 0.0, 100.0, 100.0)
+            .solver_config(config)
+            .build();
+        assert!(equals_float(
+            world.solver_config().restitution_velocity_threshold,
+            threshold
+        ));
+        let ball = world.insert(Circle::new(
+            Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
+            RigidBody {
+                mass: 1.0,
+                position: Vec2::new(0.0, 6.0),
+                acceleration: Vec2::new(0.0, -10.0),
+                ..Default::default()
+            },
+            1.0,
+        ));
+
+        for _ in 0..150 {
+            world.next_frame(0.01);
+        }
+        let body = world.get(ball).unwrap().rigid_body();
+        assert_eq!(body.velocity.y > 1.0, bounced);
+    }
+}
+
+#[test]
+fn test_broad_phases_give_the_same_simulation() {
+    // 宽检测只决定候选物体对，物体对按句柄排序后求解，换用其他宽检测不应改变模拟结果。
+    let mut worlds = vec![
+        World::builder().bounds(0.0, 0.0, 200.0, 200.0).build(),
+        World::builder()
+            .bounds(0.0, 0.0, 200.0, 200.0)
+            .broad_phase(DynamicTree::with_margin(2.0))
+            .build(),
+        World::builder()
+            .bounds(0.0, 0.0, 200.0, 200.0)
+            .broad_phase(SpatialHash::default())
+            .build(),
+        // 动态树的包围盒放大距离取自 SolverConfig。
+        World::builder()
+            .bounds(0.0, 0.0, 200.0, 200.0)
+            .dynamic_tree()
+            .solver_config(SolverConfig {
+                aabb_margin: 5.0,
+                ..Default::default()
+            })
+            .build(),
+    ];
+    for world in &mut worlds {
+        for i in 0..5 {
+            for j in 0..5 {
+                let position = Vec2::new(30.0 + i as Float * 30.0, 30.0 + j as Float * 30.0);
+                let velocity = Vec2::new((i as Float - 2.0) * 7.0, (j as Float - 2.0) * 5.0 + 3.0);
+                world.insert(get_circle(position, velocity));
+            }
+        }
+        for _ in 0..300 {
+            world.next_frame(1.0 / 10.0);
+        }
+    }
+
+    let states: Vec<Vec<_>> = worlds
+        .iter()
+        .map(|world| {
+            world
+                .iter()
+                .map(|(handle, shape)| (handle, shape.rigid_body().position))
+                .collect()
+        })
+        .collect();
+    assert_eq!(states[0], states[1]);
+    assert_eq!(states[0], states[2]);
+    assert_eq!(states[0], states[3]);
+
+    let region = Aabb {
+        left: 0.0,
+        bottom: 0.0,
+        right: 100.0,
+        top: 100.0,
+    };
+    let expected: Vec<_> = worlds[0]
+        .iter()
+        .filter(|(_, shape)| Aabb::of(*shape).overlaps(&region))
+        .map(|(handle, _)| handle)
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(worlds[0].query_region(&region), expected);
+    assert_eq!(worlds[1].query_region(&region), expected);
+    assert_eq!(worlds[2].query_region(&region), expected);
+    assert_eq!(worlds[3].query_region(&region), expected);
+}
+
+#[test]
+fn test_gravity_accelerates_bodies() {
+    let gravity = Vec2::new(0.0, -10.0);
+    let mut world = World::builder().gravity(gravity).build();
+    let falling = world.insert(get_circle(Vec2::new(0.0, 100.0), Vec2::new(1.0, 0.0)));
+    let fixed = world.insert(Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: Float::INFINITY,
+            position: Vec2::new(100.0, 100.0),
+            ..Default::default()
+        },
+        10.0,
+    ));
+    assert_eq!(world.gravity(), gravity);
+
+    for _ in 0..10 {
+        world.next_frame(0.1);
+    }
+    let body = world.get(falling).unwrap().rigid_body();
+    assert!(equals_float(body.velocity.x, 1.0));
+    assert!(equals_float(body.velocity.y, -10.0));
+    assert!(equals_float(body.position.x, 1.0));
+    assert!(equals_float(body.position.y, 95.0));
+    // 质量无穷大的物体不受重力影响
+    assert_eq!(
+        world.get(fixed).unwrap().rigid_body().position,
+        Vec2::new(100.0, 100.0)
+    );
+
+    world.set_gravity(Vec2::new(0.0, 0.0));
+    world.next_frame(0.1);
+    assert!(equals_float(
+        world.get(falling).unwrap().rigid_body().velocity.y,
+        -10.0
+    ));
+}
+
+#[test]
+fn test_forces_are_cleared_after_each_step() {
+    let mut world = World::builder().build();
+    let mut rectangle = Rectangle::new(
+        Material::default(),
+        RigidBody {
+            mass: 2.0,
+            ..Default::default()
+        },
+        10.0,
+        10.0,
+    );
+    rectangle.rigid_body_mut().inertia = 4.0;
+    let handle = world.insert(rectangle);
+
+    // 力与力矩累积后在一帧内作用
+    {
+        let body = world.get_mut(handle).unwrap().rigid_body_mut();
+        body.apply_force(Vec2::new(4.0, 0.0));
+        body.apply_force(Vec2::new(0.0, 2.0));
+        body.apply_torque(8.0);
+    }
+    world.next_frame(0.5);
+    {
+        let body = world.get(handle).unwrap().rigid_body();
+        assert_eq!(body.velocity, Vec2::new(1.0, 0.5));
+        assert!(equals_float(body.angular_velocity, 1.0));
+        assert_eq!(body.force, Vec2::new(0.0, 0.0));
+        assert!(equals_float(body.torque, 0.0));
+    }
+    world.next_frame(0.5);
+    {
+        let body = world.get(handle).unwrap().rigid_body();
+        assert_eq!(body.velocity, Vec2::new(1.0, 0.5));
+        assert!(equals_float(body.angular_velocity, 1.0));
+    }
+
+    // 作用点偏离质心的力同时产生力矩
+    {
+        let body = world.get_mut(handle).unwrap().rigid_body_mut();
+        let point = body.position + Vec2::new(0.0, 1.0);
+        body.apply_force_at_point(Vec2::new(-4.0, 0.0), point);
+        assert!(equals_float(body.torque, 4.0));
+    }
+    world.next_frame(0.5);
+    {
+        let body = world.get(handle).unwrap().rigid_body();
+        assert!(equals_float(body.velocity.x, 0.0));
+        assert!(equals_float(body.angular_velocity, 1.5));
+    }
+
+    // 冲量立即改变速度
+    {
+        let body = world.get_mut(handle).unwrap().rigid_body_mut();
+        let point = body.position + Vec2::new(1.0, 0.0);
+        body.apply_impulse(Vec2::new(0.0, 2.0), point);
+        assert!(equals_float(body.velocity.y, 1.5));
+        assert!(equals_float(body.angular_velocity, 2.0));
+    }
+}
+
+#[test]
+fn test_step_is_independent_of_frame_rate() {
+    // 步长与各帧时长都取 2 的负整数次幂，累计时间没有舍入误差。
+    let step_config = StepConfig {
+        time_step: 1.0 / 64.0,
+        ..Default::default()
+    };
+    let create_world = || {
+        let mut world = World::builder()
+            .bounds(0.0, 0.0, 100.0, 100.0)
+            .gravity(Vec2::new(0.0, -10.0))
+            .step_config(step_config)
+            .build();
+        world.insert(get_circle(Vec2::new(30.0, 80.0), Vec2::new(20.0, 0.0)));
+        world.insert(get_circle(Vec2::new(70.0, 60.0), Vec2::new(-20.0, 5.0)));
+        world
+    };
+
+    let mut slow = create_world();
+    let mut fast = create_world();
+    let mut slow_steps = 0;
+    for _ in 0..64 {
+        slow_steps += slow.step(1.0 / 32.0);
+    }
+    let mut fast_steps = 0;
+    for i in 0..192 {
+        fast_steps += fast.step(if i % 3 == 0 { 1.0 / 64.0 } else { 1.0 / 128.0 });
+    }
+
+    assert_eq!(slow_steps, 128);
+    assert_eq!(fast_steps, 128);
+    assert!(equals_float(slow.interpolation_alpha(), 0.0));
+    assert!(equals_float(fast.interpolation_alpha(), 0.0));
+    for ((_, shape1), (_, shape2)) in slow.iter().zip(fast.iter()) {
+        assert_eq!(shape1.rigid_body().position, shape2.rigid_body().position);
+        assert_eq!(shape1.rigid_body().velocity, shape2.rigid_body().velocity);
+    }
+}
+
+#[test]
+fn test_step_caps_steps_and_interpolates() {
+    let mut world = World::builder()
+        .step_config(StepConfig {
+            time_step: 0.25,
+            max_steps: 4,
+            sub_steps: 1,
+        })
+        .build();
+    let handle = world.insert(get_circle(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0)));
+
+    // 尚未运行任何一步时，前后位姿相同
+    assert_eq!(world.step(0.125), 0);
+    assert!(equals_float(world.interpolation_alpha(), 0.5));
+    assert_eq!(
+        world.interpolated_transform(handle).unwrap().position,
+        Vec2::new(0.0, 0.0)
+    );
+
+    // 累计 0.375 后运行一步，剩余半步的时间用于在前后两步的位姿间插值
+    assert_eq!(world.step(0.25), 1);
+    assert!(equals_float(world.interpolation_alpha(), 0.5));
+    assert_eq!(
+        world.previous_transform(handle).unwrap().position,
+        Vec2::new(0.0, 0.0)
+    );
+    assert_eq!(
+        world.get(handle).unwrap().rigid_body().position,
+        Vec2::new(1.0, 0.0)
+    );
+    assert_eq!(
+        world.interpolated_transform(handle).unwrap().position,
+        Vec2::new(0.5, 0.0)
+    );
+
+    // 每次最多运行 max_steps 步，超出的整步被舍弃
+    assert_eq!(world.step(10.0), 4);
+    assert!(world.interpolation_alpha() < 1.0);
+    assert_eq!(
+        world.get(handle).unwrap().rigid_body().position,
+        Vec2::new(5.0, 0.0)
+    );
+}
+
+#[test]
+fn test_step_applies_forces_to_every_sub_step() {
+    let mut world = World::builder()
+        .step_config(StepConfig {
+            time_step: 0.5,
+            max_steps: 8,
+            sub_steps: 4,
+        })
+        .build();
+    let handle = world.insert(get_circle(Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0)));
+
+    world
+        .get_mut(handle)
+        .unwrap()
+        .rigid_body_mut()
+        .apply_force(Vec2::new(2.0, 0.0));
+    assert_eq!(world.step(1.0), 2);
+    let body = world.get(handle).unwrap().rigid_body();
+    assert!(equals_float(body.velocity.x, 2.0));
+    assert!(equals_float(body.position.x, 1.0));
+    assert_eq!(body.force, Vec2::new(0.0, 0.0));
+
+    // 外力在调用结束后清零
+    assert_eq!(world.step(0.5), 1);
+    assert!(equals_float(
+        world.get(handle).unwrap().rigid_body().velocity.x,
+        2.0
+    ));
+}
+
+#[test]
+fn test_step_keeps_forces_until_a_step_runs() {
+    let mut world = World::builder()
+        .step_config(StepConfig {
+            time_step: 0.5,
+            max_steps: 8,
+            sub_steps: 4,
+        })
+        .build();
+    let handle = world.insert(get_circle(Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0)));
+    let apply_force = |world: &mut World| {
+        world
+            .get_mut(handle)
+            .unwrap()
+            .rigid_body_mut()
+            .apply_force(Vec2::new(2.0, 0.0));
+    };
+
+    // 不足一步时不运行，外力保留到下一次调用
+    apply_force(&mut world);
+    assert_eq!(world.step(0.25), 0);
+    assert_eq!(
+        world.get(handle).unwrap().rigid_body().force,
+        Vec2::new(2.0, 0.0)
+    );
+
+    // 两次施加的外力累加后作用于这一步
+    apply_force(&mut world);
+    assert_eq!(world.step(0.25), 1);
+    let body = world.get(handle).unwrap().rigid_body();
+    assert!(equals_float(body.velocity.x, 2.0));
+    assert_eq!(body.force, Vec2::new(0.0, 0.0));
+}
+
+#[test]
+fn test_step_ignores_non_positive_time_step() {
+    // 步长为 0 时不运行任何一步，插值比例保持为有限值；改回正数后照常推进。
+    let mut world = World::builder()
+        .step_config(StepConfig {
+            time_step: 0.0,
+            max_steps: 8,
+            sub_steps: 1,
+        })
+        .build();
+    let handle = world.insert(get_circle(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0)));
+
+    assert_eq!(world.step(1.0), 0);
+    assert_eq!(world.interpolation_alpha(), 0.0);
+    assert_eq!(
+        world.get(handle).unwrap().rigid_body().position,
+        Vec2::new(0.0, 0.0)
+    );
+
+    world.step_config_mut().time_step = -0.25;
+    assert_eq!(world.step(1.0), 0);
+    assert_eq!(world.interpolation_alpha(), 0.0);
+
+    world.step_config_mut().time_step = 0.25;
+    assert_eq!(world.step(0.25), 1);
+    assert!(equals_float(
+        world.get(handle).unwrap().rigid_body().position.x,
+        1.0
+    ));
+}
+
+#[test]
+fn test_step_runs_at_least_one_sub_step() {
+    // 子步数为 0 时按 1 处理，报告的步数与实际推进的时间一致。
+    let mut world = World::builder()
+        .step_config(StepConfig {
+            time_step: 0.25,
+            max_steps: 8,
+            sub_steps: 0,
+        })
+        .build();
+    let handle = world.insert(get_circle(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0)));
+
+    assert_eq!(world.step(0.5), 2);
+    assert!(equals_float(
+        world.get(handle).unwrap().rigid_body().position.x,
+        2.0
+    ));
+}
+
+#[test]
+fn test_circle_rests_on_static_obstacle() {
+    // 盒子中的静态矩形不受重力与碰撞影响，小球落在其上并静止。
+    let mut world = World::builder()
+        .bounds(0.0, 0.0, 100.0, 100.0)
+        .gravity(Vec2::new(0.0, -10.0))
+        .build();
+    let obstacle = world.insert(Rectangle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(50.0, 30.0),
+            velocity: Vec2::new(3.0, 0.0),
+            body_type: BodyType::Static,
+            ..Default::default()
+        },
+        40.0,
+        20.0,
+    ));
+    let circle = world.insert(Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(50.0, 60.0),
+            ..Default::default()
+        },
+        5.0,
+    ));
+
+    for _ in 0..300 {
+        world.next_frame(1.0 / 30.0);
+    }
+
+    let obstacle = world.get(obstacle).unwrap().rigid_body();
+    assert_eq!(obstacle.position, Vec2::new(50.0, 30.0));
+    assert_eq!(obstacle.velocity, Vec2::new(0.0, 0.0));
+    let circle = world.get(circle).unwrap().rigid_body();
+    assert!((circle.position.y - 45.0).abs() < 0.05);
+    assert!(circle.velocity.length() < 1e-3);
+}
+
+#[test]
+fn test_kinematic_body_pushes_dynamic_body() {
+    // 运动学物体按设定的速度运动，推动小球而自身速度不变，也不受重力与墙体的影响。
+    let mut world = World::builder()
+        .wall(Wall::new(Material::default(), 0.0, Orientation::Bottom))
+        .gravity(Vec2::new(0.0, -10.0))
+        .build();
+    let pusher = world.insert(Rectangle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(0.0, 5.0),
+            velocity: Vec2::new(2.0, 0.0),
+            body_type: BodyType::Kinematic,
+            ..Default::default()
+        },
+        10.0,
+        10.0,
+    ));
+    let circle = world.insert(Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(20.0, 5.0),
+            ..Default::default()
+        },
+        5.0,
+    ));
+
+    for _ in 0..300 {
+        world.next_frame(1.0 / 30.0);
+    }
+
+    let pusher = world.get(pusher).unwrap().rigid_body();
+    assert_eq!(pusher.velocity, Vec2::new(2.0, 0.0));
+    assert!(equals_float(pusher.position.x, 20.0));
+    assert!(equals_float(pusher.position.y, 5.0));
+    let circle = world.get(circle).unwrap().rigid_body();
+    assert!(circle.position.x >= 30.0 - 0.05);
+    assert!((circle.velocity.x - 2.0).abs() < 0.05);
+}
+
+#[test]
+fn test_capsule_falls_over_and_rests_on_box() {
+    // 斜放的胶囊落在静态矩形上，翻倒后平躺静止；下方的矩形与胶囊的两个端点同时接触。
+    let mut world = World::builder()
+        .bounds(0.0, 0.0, 100.0, 100.0)
+        .gravity(Vec2::new(0.0, -10.0))
+        .build();
+    world.insert(Rectangle::new(
+        Material::default(),
+        RigidBody {
+            position: Vec2::new(50.0, 20.0),
+            body_type: BodyType::Static,
+            ..Default::default()
+        },
+        60.0,
+        20.0,
+    ));
+    let capsule = world.insert(Capsule::new(
+        Material {
+            static_friction: 0.5,
+            dynamic_friction: 0.3,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(50.0, 40.0),
+            angle: 0.3,
+            ..Default::default()
+        },
+        10.0,
+        2.0,
+    ));
+
+    for _ in 0..600 {
+        world.next_frame(1.0 / 30.0);
+    }
+
+    let capsule = world.get(capsule).unwrap().rigid_body();
+    assert!((capsule.position.y - 32.0).abs() < 0.05);
+    assert!(capsule.angle.sin().abs() < 0.01);
+    assert!(capsule.velocity.length() < 1e-3);
+    assert!(capsule.angular_velocity.abs() < 1e-3);
+}
+
+#[test]
+fn test_box_slides_across_chain_joints() {
+    // 无摩擦的方块沿着由多条共线线段组成的地面滑行，经过连接处时不会被卡住或弹起。
+    let mut world = World::builder()
+        .bounds(0.0, 0.0, 100.0, 100.0)
+        .gravity(Vec2::new(0.0, -10.0))
+        .build();
+    let vertices = (0..=20)
+        .map(|i| Vec2::new(90.0 - 4.0 * i as Float, 0.0))
+        .collect();
+    let mut ground = Chain::polyline(
+        Material::default(),
+        RigidBody {
+            position: Vec2::new(0.0, 20.0),
+            ..Default::default()
+        },
+        vertices,
+    )
+    .unwrap();
+    ground.one_sided = true;
+    let ground = world.insert_chain(&ground);
+    assert_eq!(world.get(ground).unwrap().fixtures().len(), 20);
+    let rect = world.insert(Rectangle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(15.0, 22.0),
+            velocity: Vec2::new(10.0, 0.0),
+            ..Default::default()
+        },
+        4.0,
+        4.0,
+    ));
+
+    for _ in 0..150 {
+        world.next_frame(1.0 / 30.0);
+    }
+
+    let rect = world.get(rect).unwrap().rigid_body();
+    assert!((rect.position.x - 65.0).abs() < 0.5);
+    assert!((rect.position.y - 22.0).abs() < 0.05);
+    assert!((rect.velocity.x - 10.0).abs() < 0.05);
+    assert!(rect.velocity.y.abs() < 0.05);
+    assert!(rect.angle.abs() < 0.01);
+}
+
+#[test]
+fn test_compound_body_rests_on_both_ends() {
+    // 两个圆组成的哑铃斜着落到地面上，两端先后着地后平躺静止；同一物体的夹具之间不产生接触。
+    let mut world = World::builder()
+        .bounds(0.0, 0.0, 100.0, 100.0)
+        .gravity(Vec2::new(0.0, -10.0))
+        .build();
+    let mut dumbbell = Body::new(RigidBody {
+        position: Vec2::new(50.0, 30.0),
+        angle: 0.3,
+        ..Default::default()
+    });
+    for offset in [-10.0, 0.0, 10.0] {
+        dumbbell.add_fixture(Fixture::new(
+            Circle::new(
+                Material {
+                    static_friction: 0.5,
+                    dynamic_friction: 0.3,
+                    density: 1.0,
+                    ..Default::default()
+                },
+                RigidBody {
+                    position: Vec2::new(offset, 0.0),
+                    ..Default::default()
+                },
+                2.0,
+            )
+            .into(),
+        ));
+    }
+    let dumbbell = world.insert_body(dumbbell);
+    assert!(equals_float(
+        world.get(dumbbell).unwrap().rigid_body().mass,
+        3.0 * PI * 4.0
+    ));
+
+    for _ in 0..600 {
+        world.next_frame(1.0 / 30.0);
+    }
+
+    let body = world.get(dumbbell).unwrap();
+    for fixture in body.fixtures() {
+        assert!((fixture.shape.rigid_body().position.y - 2.0).abs() < 0.05);
+    }
+    let body = body.rigid_body();
+    assert!(body.angle.sin().abs() < 0.01);
+    assert!(body.velocity.length() < 1e-3);
+    assert!(body.angular_velocity.abs() < 1e-3);
+}
+
+#[test]
+fn test_fixtures_are_filtered_and_share_broad_phase_queries() {
+    // 碎片的夹具不与玩家所属的类别碰撞，穿过玩家后落到地面上；夹具各自参与宽检测，查询时按物体返回。
+    let mut world = World::builder()
+        .bounds(0.0, 0.0, 100.0, 100.0)
+        .gravity(Vec2::new(0.0, -10.0))
+        .build();
+    let mut player = Body::new(RigidBody {
+        position: Vec2::new(50.0, 10.0),
+        body_type: BodyType::Static,
+        ..Default::default()
+    });
+    for offset in [-10.0, 10.0] {
+        let mut fixture = Fixture::new(Shape::from(Rectangle::new(
+            Material::default(),
+            RigidBody {
+                position: Vec2::new(offset, 0.0),
+                ..Default::default()
+            },
+            10.0,
+            10.0,
+        )));
+        fixture.filter.category_bits = 0x0002;
+        player.add_fixture(fixture);
+    }
+    let player = world.insert_body(player);
+
+    let mut debris = Body::from_shape(Shape::from(Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(40.0, 40.0),
+            ..Default::default()
+        },
+        5.0,
+    )));
+    debris.fixtures_mut()[0].filter.mask_bits = !0x0002;
+    let debris = world.insert_body(debris);
+
+    world.next_frame(1.0 / 30.0);
+    let region = Aabb {
+        left: 30.0,
+        bottom: 0.0,
+        right: 70.0,
+        top: 20.0,
+    };
+    assert_eq!(world.query_region(&region), vec![player]);
+
+    let mut began = Vec::new();
+    for _ in 0..120 {
+        world.next_frame(1.0 / 30.0);
+        began.extend_from_slice(world.began_overlaps());
+    }
+    assert_eq!(began, vec![(player, debris)]);
+    let debris = world.get(debris).unwrap().rigid_body();
+    assert!((debris.position.y - 5.0).abs() < 0.05);
+    assert!(debris.velocity.length() < 1e-3);
+}
+
+#[test]
+fn test_replacing_body_reconciles_fixture_proxies() {
+    // 经 get_mut 把两个夹具的物体整个替换为单个形状后，旧夹具的代理从宽检测中移除，新夹具重新分配代理。
+    let mut world = World::builder().build();
+    let mut player = Body::new(RigidBody {
+        position: Vec2::new(50.0, 10.0),
+        body_type: BodyType::Static,
+        ..Default::default()
+    });
+    for offset in [-10.0, 10.0] {
+        player.add_fixture(Fixture::new(Shape::from(Rectangle::new(
+            Material::default(),
+            RigidBody {
+                position: Vec2::new(offset, 0.0),
+                ..Default::default()
+            },
+            10.0,
+            10.0,
+        ))));
+    }
+    let player = world.insert_body(player);
+    let ball = world.insert(Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(60.0, 17.0),
+            ..Default::default()
+        },
+        3.0,
+    ));
+    world.next_frame(1.0 / 30.0);
+    assert_eq!(world.began_overlaps(), &[(player, ball)]);
+
+    *world.get_mut(player).unwrap() = Body::from_shape(Shape::from(Circle::new(
+        Material::default(),
+        RigidBody {
+            position: Vec2::new(50.0, 10.0),
+            body_type: BodyType::Static,
+            ..Default::default()
+        },
+        2.0,
+    )));
+    world.next_frame(1.0 / 30.0);
+    assert_eq!(world.ended_overlaps(), &[(player, ball)]);
+    let old_fixture = Aabb {
+        left: 35.0,
+        bottom: 5.0,
+        right: 45.0,
+        top: 15.0,
+    };
+    assert!(world.query_region(&old_fixture).is_empty());
+    let new_fixture = Aabb {
+        left: 49.0,
+        bottom: 9.0,
+        right: 51.0,
+        top: 11.0,
+    };
+    assert_eq!(world.query_region(&new_fixture), vec![player]);
+}
+
+#[test]
+fn test_bullet_ignores_its_shooter_and_group() {
+    // 子弹与射手、队友同属一个负组号，从射手体内射出，穿过队友后停在靶子上，射手保持静止。
+    let mut world = World::builder().bounds(0.0, 0.0, 100.0, 100.0).build();
+    let squad = CollisionFilter {
+        group_index: -1,
+        ..Default::default()
+    };
+    let shooter = world.insert(Rectangle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(20.0, 50.0),
+            ..Default::default()
+        },
+        10.0,
+        10.0,
+    ));
+    let teammate = world.insert(Rectangle::new(
+        Material::default(),
+        RigidBody {
+            position: Vec2::new(45.0, 50.0),
+            body_type: BodyType::Static,
+            ..Default::default()
+        },
+        1.0,
+        20.0,
+    ));
+    world.insert(Rectangle::new(
+        Material::default(),
+        RigidBody {
+            position: Vec2::new(70.0, 50.0),
+            body_type: BodyType::Static,
+            ..Default::default()
+        },
+        2.0,
+        20.0,
+    ));
+    let bullet = world.insert(Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: 0.1,
+            position: Vec2::new(20.0, 50.0),
+            velocity: Vec2::new(200.0, 0.0),
+            bullet: true,
+            ..Default::default()
+        },
+        1.0,
+    ));
+    for handle in [shooter, teammate, bullet] {
+        world.get_mut(handle).unwrap().set_filter(squad);
+    }
+
+    for _ in 0..30 {
+        world.next_frame(1.0 / 60.0);
+    }
+
+    let shooter = world.get(shooter).unwrap().rigid_body();
+    assert_eq!(shooter.position, Vec2::new(20.0, 50.0));
+    assert_eq!(shooter.velocity, Vec2::new(0.0, 0.0));
+    let bullet = world.get(bullet).unwrap().rigid_body();
+    assert!((bullet.position.x - 68.0).abs() < 0.05);
+    assert!(bullet.velocity.x.abs() < 1e-6);
+}
+
+#[test]
+fn test_walls_are_filtered() {
+    // 四面墙体属于类别 0x0002，掩码排除该类别的圆穿过地面落出边界，另一个圆停在地面上。
+    let mut world = World::builder()
+        .bounds(0.0, 0.0, 100.0, 100.0)
+        .wall_filter(CollisionFilter {
+            category_bits: 0x0002,
+            ..Default::default()
+        })
+        .gravity(Vec2::new(0.0, -10.0))
+        .wall_material(Material::default())
+        .build();
+    let circle = |x: Float| {
+        Circle::new(
+            Material::default(),
+            RigidBody {
+                mass: 1.0,
+                position: Vec2::new(x, 10.0),
+                ..Default::default()
+            },
+            2.0,
+        )
+    };
+    let ghost = world.insert(circle(30.0));
+    world.get_mut(ghost).unwrap().set_filter(CollisionFilter {
+        mask_bits: !0x0002,
+        ..Default::default()
+    });
+    let ball = world.insert(circle(70.0));
+
+    for _ in 0..60 {
+        world.next_frame(1.0 / 30.0);
+    }
+
+    assert!(world.get(ghost).unwrap().rigid_body().position.y < -10.0);
+    let ball = world.get(ball).unwrap().rigid_body();
+    assert!((ball.position.y - 2.0).abs() < 0.05);
+    assert!(ball.velocity.length() < 1e-3);
+}
+
+// Helper Functions
+
+fn get_circle(position: Vec2, velocity: Vec2) -> Shape {
+    Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            velocity,
+            ..Default::default()
+        },
+        10.0,
+    )
+    .into()
+}
+
+fn get_rectangle(position: Vec2, velocity: Vec2) -> Rectangle {
+    Rectangle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            velocity,
+            ..Default::default()
+        },
+        10.0,
+        10.0,
+    )
+}
+
+fn assert_stack_stays_at_rest(config: SolverConfig, scale: Float) {
+    let material = Material {
+        restitution: 0.0,
+        static_friction: 0.5,
+        dynamic_friction: 0.5,
+        ..Default::default()
+    };
+    let mut world = World::builder()
+        .wall(Wall::new(material, 0.0, Orientation::Bottom))
+        .solver_config(config)
+        .build();
+    let boxes: Vec<_> = (0..3)
+        .map(|i| {
+            world.insert(Rectangle::new(
+                material,
+                RigidBody {
+                    mass: 1.0,
+                    position: Vec2::new(0.0, 0.5 + i as Float) * scale,
+                    acceleration: Vec2::new(0.0, -10.0) * scale,
+                    ..Default::default()
+                },
+                scale,
+                scale,
+            ))
+        })
+        .collect();
+
+    for _ in 0..600 {
+        world.next_frame(1.0 / 60.0);
+    }
+
+    for (i, &handle) in boxes.iter().enumerate() {
+        let body = world.get(handle).unwrap().rigid_body();
+        assert!(body.position.x.abs() < 0.05 * scale);
+        assert!((body.position.y - (0.5 + i as Float) * scale).abs() < 0.05 * scale);
+        assert!(body.velocity.length() < 1e-3 * scale);
+        assert!(body.angle.abs() < 0.05);
+    }
+}
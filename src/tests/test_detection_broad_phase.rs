@@ -1,6 +1,13 @@
+use rand::random;
+
 use crate::{
-    algebra::Vec2,
-    detection_broad_phase::{detect_by_broad_phase, ShapeIndexPair},
+    algebra::{Float, Vec2},
+    body_set::BodySet,
+    box2d::SolverConfig,
+    detection_broad_phase::{
+        detect_by_broad_phase, detect_by_spatial_hash, Aabb, BroadPhase, DynamicTree,
+        ShapeIndexPair, SpatialHash, SpatialHashError, SweepAndPrune,
+    },
     detection_narrow_phase::detect_collision_circle_and_circle,
     shapes::{Circle, Material, RigidBody, RigidBodyLike},
 };
@@ -9,23 +16,31 @@ use crate::{
 fn test_detect_by_broad_phase_circle() {
     let zero = Vec2::new(0.0, 0.0);
     let circle1 = Circle::new(
-        Material { restitution: 1.0 },
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
         RigidBody {
             position: Vec2::new(10.0, 10.0),
             mass: 1.0,
             velocity: zero,
             acceleration: zero,
+            ..Default::default()
         },
         10.0,
     );
 
     let mut circle2 = Circle::new(
-        Material { restitution: 1.0 },
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
         RigidBody {
             position: Vec2::new(20.0, 10.0),
             mass: 1.0,
             velocity: zero,
             acceleration: zero,
+            ..Default::default()
         },
         10.0,
     );
@@ -65,6 +80,342 @@ fn test_detect_by_broad_phase_circle() {
 
         assert_eq!(results.len(), 1);
         assert_eq!(results[0], ShapeIndexPair(0, 1));
-        assert!(detect_collision_circle_and_circle(&circle1, &circle2).is_none());
+        assert!(
+            detect_collision_circle_and_circle(&circle1, &circle2, &SolverConfig::default())
+                .is_none()
+        );
+    }
+}
+
+#[test]
+fn test_detect_by_spatial_hash_matches_detect_by_broad_phase() {
+    fn random_float(a: Float, b: Float) -> Float {
+        (b - a) * random::<Float>() + a
+    }
+    fn normalize(pairs: Vec<ShapeIndexPair>) -> Vec<(usize, usize)> {
+        let mut pairs: Vec<_> = pairs
+            .into_iter()
+            .map(|ShapeIndexPair(index1, index2)| (index1.min(index2), index1.max(index2)))
+            .collect();
+        pairs.sort();
+        pairs
     }
+
+    // 等半径的圆排成网格，一部分恰好相切，一部分随机错开；坐标包含负数。
+    let circles: Vec<Circle> = (0..400)
+        .map(|i| {
+            let lattice = Vec2::new((i % 20) as Float * 10.0 - 100.0, (i / 20) as Float * 10.0);
+            let offset = if random::<bool>() {
+                Vec2::new(random_float(-2.0, 2.0), random_float(-2.0, 2.0))
+            } else {
+                Vec2::new(0.0, 0.0)
+            };
+            create_circle(lattice + offset, 5.0)
+        })
+        .collect();
+    // 相切时的判定与输入顺序有关，正反两种顺序都应一致。
+    for shapes in [
+        circles.iter().collect::<Vec<&Circle>>(),
+        circles.iter().rev().collect(),
+    ] {
+        let expected = normalize(detect_by_broad_phase(&shapes));
+        assert!(!expected.is_empty());
+
+        for cell_size in [None, Some(1.0), Some(10.0), Some(35.0), Some(1000.0)] {
+            assert_eq!(
+                normalize(detect_by_spatial_hash(&shapes, cell_size).unwrap()),
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn test_spatial_hash_cell_size_and_oversized_shapes() {
+    // 非正数或非有限的格子尺寸属于用户输入错误，返回错误而不是崩溃。
+    for cell_size in [0.0, -1.0, Float::NAN, Float::INFINITY] {
+        assert_eq!(
+            SpatialHash::new(cell_size).err(),
+            Some(SpatialHashError::InvalidCellSize)
+        );
+        assert_eq!(
+            detect_by_spatial_hash(&[&create_circle(Vec2::new(0.0, 0.0), 1.0)], Some(cell_size))
+                .err(),
+            Some(SpatialHashError::InvalidCellSize)
+        );
+    }
+
+    // 远大于格子的物体不逐格登记，否则需要遍历约 10^18 个格子；结果仍与 detect_by_broad_phase 相同。
+    let circles = vec![
+        create_circle(Vec2::new(0.0, 0.0), 1.0),
+        create_circle(Vec2::new(1e9, 0.0), 1e9),
+        create_circle(Vec2::new(1.5, 0.0), 1.0),
+        create_circle(Vec2::new(-1e9 - 0.5, 0.0), 1e9),
+        create_circle(Vec2::new(10.0, 1e8), 1.0),
+    ];
+    let shapes: Vec<&Circle> = circles.iter().collect();
+    let mut expected: Vec<_> = detect_by_broad_phase(&shapes)
+        .into_iter()
+        .map(|ShapeIndexPair(index1, index2)| (index1.min(index2), index1.max(index2)))
+        .collect();
+    expected.sort();
+    let pairs: Vec<_> = detect_by_spatial_hash(&shapes, Some(1.0))
+        .unwrap()
+        .into_iter()
+        .map(|ShapeIndexPair(index1, index2)| (index1, index2))
+        .collect();
+    assert_eq!(pairs, expected);
+
+    let mut set = BodySet::new();
+    let mut hash = SpatialHash::new(1.0).unwrap();
+    for circle in circles {
+        let aabb = Aabb::of(&circle);
+        hash.insert(set.insert(circle), aabb);
+    }
+    hash.update_pairs();
+    let handles = set.handles();
+    let expected: Vec<_> = expected
+        .into_iter()
+        .map(|(index1, index2)| (handles[index1], handles[index2]))
+        .collect();
+    assert_eq!(hash.pairs(), expected);
+}
+
+#[test]
+fn test_broad_phases_report_overlap_deltas() {
+    assert_reports_overlap_deltas(SweepAndPrune::new());
+    assert_reports_overlap_deltas(DynamicTree::with_margin(0.5));
+    assert_reports_overlap_deltas(SpatialHash::default());
+    assert_reports_overlap_deltas(SpatialHash::new(3.0).unwrap());
+}
+
+#[test]
+fn test_broad_phases_match_detect_by_broad_phase() {
+    assert_matches_detect_by_broad_phase(SweepAndPrune::new());
+    assert_matches_detect_by_broad_phase(DynamicTree::with_margin(0.5));
+    assert_matches_detect_by_broad_phase(DynamicTree::with_margin(0.0));
+    assert_matches_detect_by_broad_phase(SpatialHash::default());
+    assert_matches_detect_by_broad_phase(SpatialHash::new(50.0).unwrap());
+}
+
+#[test]
+fn test_dynamic_tree_stays_balanced() {
+    // 沿一条直线依次插入，不做旋转时树会退化成链表。
+    let mut shapes = BodySet::new();
+    let mut tree = DynamicTree::default();
+    for i in 0..1024 {
+        let shape = create_circle(Vec2::new(i as Float * 10.0, 0.0), 4.0);
+        let aabb = Aabb::of(&shape);
+        tree.insert(shapes.insert(shape), aabb);
+    }
+    assert!(tree.height() <= 20);
+
+    let handles = shapes.handles().to_vec();
+    for &handle in handles.iter().step_by(2) {
+        tree.remove(handle);
+        shapes.remove(handle);
+    }
+    assert!(tree.height() <= 20);
+}
+
+#[test]
+fn test_dynamic_tree_tracks_overlaps_within_fat_aabbs() {
+    // 放大后的包围盒足够大时，物体移动不会重新插入树中；实际的包围盒开始或不再相交时仍须报告。
+    let mut shapes = BodySet::new();
+    let mut tree = DynamicTree::with_margin(10.0);
+    let handle1 = shapes.insert(create_circle(Vec2::new(0.0, 0.0), 10.0));
+    let handle2 = shapes.insert(create_circle(Vec2::new(25.0, 0.0), 10.0));
+    for (handle, shape) in shapes.iter() {
+        tree.insert(handle, Aabb::of(shape));
+    }
+    tree.update_pairs();
+    assert!(tree.pairs().is_empty());
+
+    shapes.get_mut(handle2).unwrap().rigid_body_mut().position = Vec2::new(18.0, 0.0);
+    tree.set_aabb(handle2, Aabb::of(shapes.get(handle2).unwrap()));
+    tree.update_pairs();
+    assert_eq!(tree.began(), &[(handle1, handle2)]);
+
+    tree.update_pairs();
+    assert!(tree.began().is_empty());
+    assert_eq!(tree.pairs(), vec![(handle1, handle2)]);
+
+    shapes.get_mut(handle2).unwrap().rigid_body_mut().position = Vec2::new(25.0, 0.0);
+    tree.set_aabb(handle2, Aabb::of(shapes.get(handle2).unwrap()));
+    tree.update_pairs();
+    assert_eq!(tree.ended(), &[(handle1, handle2)]);
+    assert!(tree.pairs().is_empty());
+}
+
+#[test]
+fn test_broad_phases_query_region() {
+    fn random_float(a: Float, b: Float) -> Float {
+        (b - a) * random::<Float>() + a
+    }
+
+    let mut shapes = BodySet::new();
+    let mut sap = SweepAndPrune::new();
+    let mut tree = DynamicTree::with_margin(1.0);
+    for _ in 0..100 {
+        let shape = create_circle(
+            Vec2::new(random_float(0.0, 200.0), random_float(0.0, 200.0)),
+            random_float(1.0, 10.0),
+        );
+        let aabb = Aabb::of(&shape);
+        let handle = shapes.insert(shape);
+        sap.insert(handle, aabb);
+        tree.insert(handle, aabb);
+    }
+
+    for _ in 0..20 {
+        let (x, y) = (random_float(0.0, 200.0), random_float(0.0, 200.0));
+        let region = Aabb {
+            left: x,
+            bottom: y,
+            right: x + random_float(0.0, 50.0),
+            top: y + random_float(0.0, 50.0),
+        };
+        let mut expected: Vec<_> = shapes
+            .iter()
+            .filter(|(_, shape)| Aabb::of(*shape).overlaps(&region))
+            .map(|(handle, _)| handle)
+            .collect();
+        expected.sort();
+
+        assert_eq!(sap.query(&region), expected);
+        assert_eq!(tree.query(&region), expected);
+    }
+}
+
+// Helper Functions
+
+fn assert_reports_overlap_deltas(mut sap: impl BroadPhase) {
+    let mut shapes = BodySet::new();
+    let handle1 = shapes.insert(create_circle(Vec2::new(0.0, 0.0), 10.0));
+    let handle2 = shapes.insert(create_circle(Vec2::new(30.0, 0.0), 10.0));
+    for (handle, shape) in shapes.iter() {
+        sap.insert(handle, Aabb::of(shape));
+    }
+    let update = |shapes: &BodySet<Circle>, sap: &mut dyn BroadPhase| {
+        for (handle, shape) in shapes.iter() {
+            sap.set_aabb(handle, Aabb::of(shape));
+        }
+        sap.update_pairs();
+    };
+
+    // 两圆相离
+    update(&shapes, &mut sap);
+    assert!(sap.pairs().is_empty());
+    assert!(sap.began().is_empty());
+    assert!(sap.ended().is_empty());
+
+    // 两圆相切：不算相交
+    shapes.get_mut(handle2).unwrap().rigid_body_mut().position = Vec2::new(20.0, 0.0);
+    update(&shapes, &mut sap);
+    assert!(sap.pairs().is_empty());
+    assert!(sap.began().is_empty());
+
+    // 开始相交，只报告一次
+    shapes.get_mut(handle2).unwrap().rigid_body_mut().position = Vec2::new(15.0, 5.0);
+    update(&shapes, &mut sap);
+    assert_eq!(sap.pairs(), vec![(handle1, handle2)]);
+    assert_eq!(sap.began(), &[(handle1, handle2)]);
+    assert!(sap.ended().is_empty());
+
+    update(&shapes, &mut sap);
+    assert_eq!(sap.pairs(), vec![(handle1, handle2)]);
+    assert!(sap.began().is_empty());
+
+    // 沿 y 轴分开后相切，不再相交
+    shapes.get_mut(handle2).unwrap().rigid_body_mut().position = Vec2::new(15.0, 20.0);
+    update(&shapes, &mut sap);
+    assert!(sap.pairs().is_empty());
+    assert_eq!(sap.ended(), &[(handle1, handle2)]);
+
+    // 删除物体时，与其相交的物体对并入下一次更新的 ended
+    shapes.get_mut(handle2).unwrap().rigid_body_mut().position = Vec2::new(5.0, 5.0);
+    update(&shapes, &mut sap);
+    assert_eq!(sap.began(), &[(handle1, handle2)]);
+    sap.remove(handle1);
+    shapes.remove(handle1);
+    update(&shapes, &mut sap);
+    assert!(sap.pairs().is_empty());
+    assert!(sap.began().is_empty());
+    assert_eq!(sap.ended(), &[(handle1, handle2)]);
+}
+
+fn assert_matches_detect_by_broad_phase(mut sap: impl BroadPhase) {
+    fn random_float(a: Float, b: Float) -> Float {
+        (b - a) * random::<Float>() + a
+    }
+    fn random_circle() -> Circle {
+        create_circle(
+            Vec2::new(random_float(0.0, 200.0), random_float(0.0, 200.0)),
+            random_float(1.0, 10.0),
+        )
+    }
+
+    let mut shapes = BodySet::new();
+    for _ in 0..50 {
+        let shape = random_circle();
+        let aabb = Aabb::of(&shape);
+        sap.insert(shapes.insert(shape), aabb);
+    }
+
+    let mut previous: Vec<_> = Vec::new();
+    for frame in 0..200 {
+        for shape in shapes.bodies_mut() {
+            shape.rigid_body_mut().position +=
+                Vec2::new(random_float(-3.0, 3.0), random_float(-3.0, 3.0));
+        }
+        // 不时增删物体，被删除的槽位会被新物体复用
+        if frame % 10 == 0 {
+            let handle = shapes.handles()[frame % shapes.len()];
+            sap.remove(handle);
+            shapes.remove(handle);
+            let shape = random_circle();
+            let aabb = Aabb::of(&shape);
+            sap.insert(shapes.insert(shape), aabb);
+        }
+
+        for (handle, shape) in shapes.iter() {
+            sap.set_aabb(handle, Aabb::of(shape));
+        }
+        sap.update_pairs();
+
+        let handles = shapes.handles();
+        let mut expected: Vec<_> = detect_by_broad_phase(&shapes.bodies().iter().collect())
+            .into_iter()
+            .map(|ShapeIndexPair(index1, index2)| {
+                let (handle1, handle2) = (handles[index1], handles[index2]);
+                (handle1.min(handle2), handle1.max(handle2))
+            })
+            .collect();
+        expected.sort();
+        let pairs = sap.pairs();
+        assert_eq!(pairs, expected);
+
+        // 上一帧的物体对加上 began、减去 ended，应得到这一帧的物体对
+        let mut applied: Vec<_> = previous
+            .iter()
+            .copied()
+            .filter(|pair| !sap.ended().contains(pair))
+            .chain(sap.began().iter().copied())
+            .collect();
+        applied.sort();
+        assert_eq!(applied, pairs);
+        previous = pairs;
+    }
+}
+
+fn create_circle(position: Vec2, radius: Float) -> Circle {
+    Circle::new(
+        Material::default(),
+        RigidBody {
+            position,
+            mass: 1.0,
+            ..Default::default()
+        },
+        radius,
+    )
 }
@@ -0,0 +1,110 @@
+use crate::{
+    algebra::{equals_float, Float, Vec2},
+    box2d::World,
+    detection_continuous::find_time_of_impact,
+    shapes::{Circle, Material, Rectangle, RigidBody, RigidBodyLike},
+};
+
+#[test]
+fn test_find_time_of_impact() {
+    // 采样找到首次碰撞的区间后，二分细化到碰撞开始的时刻。
+    let time = find_time_of_impact(10, |t| t >= 0.333).unwrap();
+    assert!((time - 0.333).abs() < 1e-4);
+    assert!(time >= 0.333);
+
+    // 起始时刻已经接触，或整个过程都未碰撞，均不做处理。
+    assert!(find_time_of_impact(10, |_| true).is_none());
+    assert!(find_time_of_impact(10, |_| false).is_none());
+
+    // 碰撞区间比采样间隔更短时，采样会将其错过。
+    assert!(find_time_of_impact(2, |t| t > 0.2 && t < 0.3).is_none());
+    assert!(find_time_of_impact(20, |t| t > 0.2 && t < 0.3).is_some());
+}
+
+#[test]
+fn test_bullet_circle_does_not_tunnel_through_circle() {
+    // 小球每帧移动 10，远超两球的直径之和：未开启连续碰撞检测时直接穿过另一个球，
+    // 开启后在碰撞时刻停下，并与另一个球交换速度。
+    for bullet in [false, true] {
+        let mut world = World::builder().build();
+        let fast = world.insert(get_circle(
+            Vec2::new(-5.0, 0.0),
+            Vec2::new(1000.0, 0.0),
+            0.1,
+            bullet,
+        ));
+        let target = world.insert(get_circle(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            0.5,
+            false,
+        ));
+        world.next_frame(0.01);
+
+        let fast = world.get(fast).unwrap().rigid_body();
+        let target = world.get(target).unwrap().rigid_body();
+        if bullet {
+            assert!(fast.position.x < -0.5);
+            assert!(equals_float(fast.velocity.x, 0.0));
+            assert!(equals_float(target.velocity.x, 1000.0));
+        } else {
+            assert!(equals_float(fast.position.x, 5.0));
+            assert!(equals_float(fast.velocity.x, 1000.0));
+            assert!(equals_float(target.velocity.x, 0.0));
+        }
+    }
+}
+
+#[test]
+fn test_bullet_circle_bounces_off_thin_rectangle() {
+    // 高速小球撞向一块很薄且固定不动的板：小球停在板前并被反弹，不会穿到板的另一侧。
+    let mut world = World::builder().build();
+    let ball = world.insert(get_circle(
+        Vec2::new(-5.0, 0.3),
+        Vec2::new(800.0, 0.0),
+        0.2,
+        true,
+    ));
+    world.insert(Rectangle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: Float::INFINITY,
+            ..Default::default()
+        },
+        0.05,
+        4.0,
+    ));
+
+    world.next_frame(0.01);
+    let body = world.get(ball).unwrap().rigid_body();
+    assert!(body.position.x < 0.0);
+    assert!((body.position.x + 0.025 + 0.2).abs() < 0.05);
+    assert!(equals_float(body.velocity.x, -800.0));
+
+    for _ in 0..10 {
+        world.next_frame(0.01);
+        assert!(world.get(ball).unwrap().rigid_body().position.x < 0.0);
+    }
+}
+
+// Helper Functions
+
+fn get_circle(position: Vec2, velocity: Vec2, radius: Float, bullet: bool) -> Circle {
+    Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position,
+            velocity,
+            bullet,
+            ..Default::default()
+        },
+        radius,
+    )
+}
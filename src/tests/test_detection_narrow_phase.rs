@@ -1,42 +1,408 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
 use crate::{
-    algebra::{equals_float, Vec2, FLOADT_TOLERANCE},
-    detection_narrow_phase::detect_collision_circle_and_circle,
-    shapes::{Circle, Material, RigidBody, RigidBodyLike},
+    algebra::{equals_float, Float, Vec2, FLOADT_TOLERANCE},
+    box2d::SolverConfig,
+    detection_narrow_phase::{
+        detect_collision_circle_and_circle, detect_collision_circle_and_polygon,
+        detect_collision_circle_and_rectangle, detect_collision_rectangle_and_rectangle,
+        detect_collision_wall_and_rectangle, CollisionInfo,
+    },
+    shapes::{Circle, Material, Orientation, Polygon, Rectangle, RigidBody, RigidBodyLike, Wall},
 };
 
 #[test]
 fn test_detect_collision_circle_and_circle() {
     let zero = Vec2::new(0.0, 0.0);
     let circle1 = Circle::new(
-        Material { restitution: 1.0 },
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
         RigidBody {
             mass: 1.0,
             position: Vec2::new(10.0, 10.0),
             velocity: zero,
             acceleration: zero,
+            ..Default::default()
         },
         10.0,
     );
     let mut circle2 = Circle::new(
-        Material { restitution: 1.0 },
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
         RigidBody {
             mass: 1.0,
             position: Vec2::new(30.0, 10.0),
             velocity: zero,
             acceleration: zero,
+            ..Default::default()
         },
         10.0,
     );
 
     // 两圆相切，相切不能算碰撞，无论宽窄检测，相切无形变，等于没有施力，故不需要处理碰撞冲突。
-    assert!(detect_collision_circle_and_circle(&circle1, &circle2).is_none());
+    assert!(
+        detect_collision_circle_and_circle(&circle1, &circle2, &SolverConfig::default()).is_none()
+    );
 
     // 两圆临界相交：穿模深度为 FLOADT_TOLERANCE 时，应当能够被检测出来。
     circle2.rigid_body_mut().position = Vec2::new(30.0 - FLOADT_TOLERANCE, 10.0);
-    let result = detect_collision_circle_and_circle(&circle1, &circle2);
+    let result = detect_collision_circle_and_circle(&circle1, &circle2, &SolverConfig::default());
     assert!(result.is_some());
     let info = result.unwrap();
     assert!(info.penetration.abs() > 0.0);
     assert!(!equals_float(info.penetration, 0.0));
     assert_eq!(info.normal, Vec2::new(1.0, 0.0));
 }
+
+#[test]
+fn test_detect_collision_circle_and_circle_at_same_position() {
+    let circle1 = Circle::new(
+        Default::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(10.0, 10.0),
+            ..Default::default()
+        },
+        10.0,
+    );
+    let circle2 = Circle::new(
+        Default::default(),
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(10.0, 10.0),
+            ..Default::default()
+        },
+        5.0,
+    );
+
+    // 两圆心重合：法线不能为 NaN，退化为竖直向上，穿模深度为两半径之和。
+    let info =
+        detect_collision_circle_and_circle(&circle1, &circle2, &SolverConfig::default()).unwrap();
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    assert!(equals_float(info.penetration, 15.0));
+    assert!(info
+        .contacts
+        .iter()
+        .all(|contact| !contact.point.x.is_nan() && !contact.point.y.is_nan()));
+}
+
+#[test]
+fn test_detect_collision_circle_and_rectangle() {
+    let zero = Vec2::new(0.0, 0.0);
+    let mut circle = Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(0.0, 20.0),
+            velocity: zero,
+            acceleration: zero,
+            ..Default::default()
+        },
+        10.0,
+    );
+    let mut rect = Rectangle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position: zero,
+            velocity: zero,
+            acceleration: zero,
+            ..Default::default()
+        },
+        40.0,
+        20.0,
+    );
+
+    // 圆与矩形上边相切，不算碰撞。
+    assert!(
+        detect_collision_circle_and_rectangle(&circle, &rect, &SolverConfig::default()).is_none()
+    );
+
+    // 圆与矩形上边相交：法线由圆指向矩形。
+    circle.rigid_body_mut().position = Vec2::new(5.0, 18.0);
+    let info =
+        detect_collision_circle_and_rectangle(&circle, &rect, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 2.0));
+    assert_eq!(info.normal, Vec2::new(0.0, -1.0));
+
+    // 圆与矩形的角相交。
+    circle.rigid_body_mut().position = Vec2::new(23.0, 14.0);
+    let info =
+        detect_collision_circle_and_rectangle(&circle, &rect, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 5.0));
+    assert_eq!(info.normal, Vec2::new(-0.6, -0.8));
+
+    // 矩形旋转 90° 后竖立，其顶端变为 y = 20。
+    rect.rigid_body_mut().angle = FRAC_PI_2;
+    circle.rigid_body_mut().position = Vec2::new(0.0, 29.0);
+    let info =
+        detect_collision_circle_and_rectangle(&circle, &rect, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 1.0));
+    assert_eq!(info.normal, Vec2::new(0.0, -1.0));
+
+    // 圆心在矩形内部：沿最近的边（右边）推开，穿模深度为半径加上圆心到边的距离。
+    rect.rigid_body_mut().angle = 0.0;
+    circle.rigid_body_mut().position = Vec2::new(17.0, 2.0);
+    let info =
+        detect_collision_circle_and_rectangle(&circle, &rect, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 13.0));
+    assert_eq!(info.normal, Vec2::new(-1.0, 0.0));
+}
+
+#[test]
+fn test_detect_collision_rectangle_and_rectangle() {
+    let zero = Vec2::new(0.0, 0.0);
+    let get_rectangle = |x: f64, y: f64, angle: f64| {
+        Rectangle::new(
+            Material {
+                restitution: 1.0,
+                ..Default::default()
+            },
+            RigidBody {
+                mass: 1.0,
+                position: Vec2::new(x, y),
+                angle,
+                velocity: zero,
+                acceleration: zero,
+                ..Default::default()
+            },
+            20.0,
+            20.0,
+        )
+    };
+
+    // 两矩形的边恰好重合，相切不算碰撞。
+    let rect1 = get_rectangle(0.0, 0.0, 0.0);
+    let rect2 = get_rectangle(20.0, 5.0, 0.0);
+    assert!(
+        detect_collision_rectangle_and_rectangle(&rect1, &rect2, &SolverConfig::default())
+            .is_none()
+    );
+
+    // 两矩形的边相互重叠：裁剪后得到两个接触点，即重叠部分的两个端点。
+    let rect2 = get_rectangle(18.0, 5.0, 0.0);
+    let info =
+        detect_collision_rectangle_and_rectangle(&rect1, &rect2, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 2.0));
+    assert_eq!(info.normal, Vec2::new(1.0, 0.0));
+    assert_eq!(info.contacts.len(), 2);
+    assert!(info
+        .contacts
+        .iter()
+        .all(|contact| contact.point.y >= -5.0 - FLOADT_TOLERANCE
+            && contact.point.y <= 10.0 + FLOADT_TOLERANCE
+            && equals_float(contact.point.x, 8.0)));
+
+    // 交换两者顺序后，法线反向。
+    let info =
+        detect_collision_rectangle_and_rectangle(&rect2, &rect1, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 2.0));
+    assert_eq!(info.normal, Vec2::new(-1.0, 0.0));
+    assert_eq!(info.contacts.len(), 2);
+
+    // 旋转 45° 的矩形以顶点刺入另一矩形的边：只有一个接触点，即该顶点。
+    let diagonal = 10.0 * 2.0_f64.sqrt();
+    let rect2 = get_rectangle(10.0 + diagonal - 1.0, 0.0, FRAC_PI_4);
+    let info =
+        detect_collision_rectangle_and_rectangle(&rect1, &rect2, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 1.0));
+    assert_eq!(info.normal, Vec2::new(1.0, 0.0));
+    assert_eq!(get_points(&info), vec![Vec2::new(9.0, 0.0)]);
+
+    // 旋转后的矩形的轴外接框相交，但分离轴定理判定两者相离。
+    let rect2 = get_rectangle(
+        10.0 + diagonal / 2.0 + 5.0 + 1.0,
+        10.0 + diagonal / 2.0,
+        FRAC_PI_4,
+    );
+    assert!(
+        detect_collision_rectangle_and_rectangle(&rect1, &rect2, &SolverConfig::default())
+            .is_none()
+    );
+}
+
+#[test]
+fn test_detect_collision_wall_and_rectangle() {
+    let zero = Vec2::new(0.0, 0.0);
+    let wall = Wall::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        0.0,
+        Orientation::Bottom,
+    );
+    let mut rect = Rectangle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(0.0, 10.0),
+            velocity: zero,
+            acceleration: zero,
+            ..Default::default()
+        },
+        40.0,
+        20.0,
+    );
+
+    // 矩形的下边与墙体重合，相切不算碰撞。
+    assert!(detect_collision_wall_and_rectangle(&wall, &rect, &SolverConfig::default()).is_none());
+
+    // 矩形的下边穿入墙体：两个下方的顶点均为接触点，法线由墙体指向矩形。
+    rect.rigid_body_mut().position = Vec2::new(0.0, 8.0);
+    let info = detect_collision_wall_and_rectangle(&wall, &rect, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 2.0));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+    assert_eq!(info.contacts.len(), 2);
+    assert!(get_points(&info).contains(&Vec2::new(-20.0, -2.0)));
+    assert!(get_points(&info).contains(&Vec2::new(20.0, -2.0)));
+
+    // 矩形旋转略多于 90° 后，只有最深的顶点被视为接触点。
+    rect.rigid_body_mut().angle = FRAC_PI_2 + 0.1;
+    rect.rigid_body_mut().position = Vec2::new(0.0, 20.0);
+    let info = detect_collision_wall_and_rectangle(&wall, &rect, &SolverConfig::default()).unwrap();
+    let deepest = rect
+        .vertices()
+        .iter()
+        .copied()
+        .fold(Vec2::new(0.0, Float::INFINITY), |a, b| {
+            if b.y < a.y {
+                b
+            } else {
+                a
+            }
+        });
+    assert!(equals_float(info.penetration, -deepest.y));
+    assert_eq!(get_points(&info), vec![deepest]);
+}
+
+#[test]
+fn test_detect_collision_circle_and_polygon() {
+    // 直角三角形 (0, 0)、(6, 0)、(0, 6)，质心位于 (2, 2)。
+    let triangle = Polygon::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody::default(),
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(6.0, 0.0),
+            Vec2::new(0.0, 6.0),
+        ],
+    )
+    .unwrap();
+    let mut circle = Circle::new(
+        Material {
+            restitution: 1.0,
+            ..Default::default()
+        },
+        RigidBody {
+            mass: 1.0,
+            position: Vec2::new(2.0, -1.0),
+            ..Default::default()
+        },
+        1.0,
+    );
+
+    // 与底边相切，不算碰撞。
+    assert!(
+        detect_collision_circle_and_polygon(&circle, &triangle, &SolverConfig::default()).is_none()
+    );
+
+    // 与底边相交。
+    circle.rigid_body_mut().position = Vec2::new(2.0, -0.5);
+    let info =
+        detect_collision_circle_and_polygon(&circle, &triangle, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 0.5));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+
+    // 位于顶点 (6, 0) 的端点区域。
+    circle.rigid_body_mut().position = Vec2::new(6.6, -0.8);
+    let info =
+        detect_collision_circle_and_polygon(&circle, &triangle, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 0.0));
+    circle.rigid_body_mut().position = Vec2::new(6.3, -0.4);
+    let info =
+        detect_collision_circle_and_polygon(&circle, &triangle, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 0.5));
+    assert_eq!(info.normal, Vec2::new(-0.6, 0.8));
+
+    // 与斜边相交。
+    let diagonal = Vec2::new(1.0, 1.0) / 2.0_f64.sqrt();
+    circle.rigid_body_mut().position = Vec2::new(3.0, 3.0) + diagonal * 0.75;
+    let info =
+        detect_collision_circle_and_polygon(&circle, &triangle, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 0.25));
+    assert_eq!(info.normal, diagonal * -1.0);
+
+    // 圆心在三角形内部，最近的边为底边。
+    circle.rigid_body_mut().position = Vec2::new(2.0, 0.5);
+    let info =
+        detect_collision_circle_and_polygon(&circle, &triangle, &SolverConfig::default()).unwrap();
+    assert!(equals_float(info.penetration, 1.5));
+    assert_eq!(info.normal, Vec2::new(0.0, 1.0));
+}
+
+#[test]
+fn test_contact_features_are_stable() {
+    // 方块落在更宽的矩形上：略微移动后接触点位置改变，但特征编号保持不变，
+    // 且同一次检测中各接触点的编号互不相同。
+    let get_rectangle = |x: Float, y: Float, width: Float| {
+        Rectangle::new(
+            Material::default(),
+            RigidBody {
+                mass: 1.0,
+                position: Vec2::new(x, y),
+                ..Default::default()
+            },
+            width,
+            10.0,
+        )
+    };
+    let ground = get_rectangle(0.0, 0.0, 40.0);
+    let features = |x: Float, y: Float| {
+        let info = detect_collision_rectangle_and_rectangle(
+            &ground,
+            &get_rectangle(x, y, 10.0),
+            &SolverConfig::default(),
+        )
+        .unwrap();
+        let mut features: Vec<_> = info
+            .contacts
+            .iter()
+            .map(|contact| contact.feature)
+            .collect();
+        features.sort_by_key(|feature| feature.0);
+        features
+    };
+
+    let before = features(0.0, 9.0);
+    assert_eq!(before.len(), 2);
+    assert_ne!(before[0], before[1]);
+    assert_eq!(features(0.5, 9.2), before);
+
+    // 方块越过宽矩形的边缘后，被侧面裁剪出的接触点换用新的编号。
+    let clipped = features(18.0, 9.0);
+    assert_eq!(clipped.len(), 2);
+    assert_ne!(clipped, before);
+}
+
+// Helper Functions
+
+fn get_points(info: &CollisionInfo) -> Vec<Vec2> {
+    info.contacts.iter().map(|contact| contact.point).collect()
+}
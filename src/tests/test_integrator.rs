@@ -0,0 +1,101 @@
+use crate::{
+    algebra::{Float, Vec2},
+    box2d::{BodyHandle, Integrator, World},
+    shapes::{Circle, Material, RigidBody, RigidBodyLike},
+};
+
+const INTEGRATORS: [Integrator; 4] = [
+    Integrator::ConstantAcceleration,
+    Integrator::SemiImplicitEuler,
+    Integrator::VelocityVerlet,
+    Integrator::RungeKutta4,
+];
+
+#[test]
+fn test_energy_of_harmonic_oscillator() {
+    // 质量为 1、劲度系数为 1 的弹簧振子，周期为 2π，模拟 10 个周期后比较能量与初始能量之比。
+    let ratios: Vec<Float> = INTEGRATORS
+        .iter()
+        .map(|&integrator| {
+            let mut world = World::builder()
+                .integrator(integrator)
+                .force_field(|body| -body.position)
+                .build();
+            let handle = insert_body(&mut world, Vec2::new(1.0, 0.0), Vec2::new(0.0, 0.0));
+
+            let energy = |world: &World| {
+                let body = world.get(handle).unwrap().rigid_body();
+                (body.velocity * body.velocity + body.position * body.position) / 2.0
+            };
+            let initial_energy = energy(&world);
+            let mut max_ratio: Float = 0.0;
+            for _ in 0..628 {
+                world.next_frame(0.1);
+                max_ratio = max_ratio.max((energy(&world) / initial_energy - 1.0).abs());
+            }
+            max_ratio
+        })
+        .collect();
+
+    // 匀加速积分的能量持续增长
+    assert!(ratios[0] > 1.0);
+    // 辛积分的能量在真实值附近小幅振荡，不随时间漂移
+    assert!(ratios[1] < 0.06);
+    assert!(ratios[2] < 0.005);
+    assert!(ratios[3] < 1e-4);
+}
+
+#[test]
+fn test_projectile() {
+    // 重力恒定时，除半隐式欧拉外的积分方式都与解析解一致，机械能守恒。
+    let gravity = Vec2::new(0.0, -10.0);
+    let (position, velocity) = (Vec2::new(0.0, 0.0), Vec2::new(3.0, 20.0));
+    let (frames, duration) = (40, 0.1);
+    let time = frames as Float * duration;
+    let expected = position + velocity * time + gravity * (time * time) / 2.0;
+
+    for &integrator in INTEGRATORS.iter() {
+        let mut world = World::builder()
+            .integrator(integrator)
+            .gravity(gravity)
+            .build();
+        let handle = insert_body(&mut world, position, velocity);
+
+        let energy = |world: &World| {
+            let body = world.get(handle).unwrap().rigid_body();
+            body.velocity * body.velocity / 2.0 - gravity * body.position
+        };
+        let initial_energy = energy(&world);
+        for _ in 0..frames {
+            world.next_frame(duration);
+        }
+
+        let body = world.get(handle).unwrap().rigid_body();
+        let error = (body.position - expected).length();
+        let energy_error = (energy(&world) - initial_energy).abs();
+        if integrator == Integrator::SemiImplicitEuler {
+            // 速度精确而位置落后 g·dt·t/2，误差随 dt 一阶收敛，能量误差即该高度差对应的势能
+            assert!((error - 10.0 * duration * time / 2.0).abs() < 1e-9);
+            assert!((energy_error - 10.0 * error).abs() < 1e-9);
+        } else {
+            assert!(error < 1e-9);
+            assert!(energy_error < 1e-9);
+        }
+        assert!((body.velocity - (velocity + gravity * time)).length() < 1e-9);
+    }
+}
+
+// Helper Functions
+
+fn insert_body(world: &mut World, position: Vec2, velocity: Vec2) -> BodyHandle {
+    world.insert(Circle::new(
+        Material::default(),
+        RigidBody {
+            mass: 1.0,
+            position,
+            velocity,
+            ..Default::default()
+        },
+        0.5,
+    ))
+}